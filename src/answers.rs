@@ -0,0 +1,228 @@
+use std::{
+	collections::HashMap,
+	fs::read_to_string,
+	path::Path
+};
+
+use crate::results::{DayResults, Status};
+
+// Expected answers live in a small TOML subset: a list of [[answer]] tables with day, part and expected keys
+#[derive(Default)]
+pub struct Answers {
+	expected: HashMap<(i32, i32), String>
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+	Pass,
+	Fail,
+	Missing
+}
+
+impl Verdict {
+	pub fn label(&self) -> &'static str {
+		match self {
+			Verdict::Pass => return "PASS",
+			Verdict::Fail => return "FAIL",
+			Verdict::Missing => return "MISSING"
+		}
+	}
+}
+
+pub struct Check {
+	pub day: i32,
+	pub part: i32,
+	pub answer: String,
+	pub expected: Option<String>,
+	pub verdict: Verdict
+}
+
+#[derive(Default)]
+struct Entry {
+	day: Option<i32>,
+	part: Option<i32>,
+	expected: Option<String>
+}
+
+impl Answers {
+	pub fn load(filename: impl AsRef<Path>) -> Result<Answers, String> {
+		let filename = filename.as_ref();
+		let data = read_to_string(filename).map_err(|e| format!("couldn't read {}: {}", filename.display(), e))?;
+		return Answers::parse(&data).map_err(|e| format!("{}: {}", filename.display(), e));
+	}
+
+	pub fn parse(data: &str) -> Result<Answers, String> {
+		let mut expected = HashMap::new();
+		let mut entry: Option<(usize, Entry)> = None;
+
+		for (index, raw_line) in data.lines().enumerate() {
+			let line_number = index + 1;
+			let line = strip_comment(raw_line).trim();
+
+			if line.is_empty() { continue; }
+
+			if line == "[[answer]]" {
+				if let Some((start, finished)) = entry.take() { insert_entry(&mut expected, start, finished)?; }
+				entry = Some((line_number, Entry::default()));
+				continue;
+			}
+
+			let (key, value) = match line.split_once('=') {
+				Some((key, value)) => (key.trim(), value.trim()),
+				None => return Err(format!("line {}: expected `key = value` or [[answer]]", line_number))
+			};
+
+			let current = match entry.as_mut() {
+				Some((_, current)) => current,
+				None => return Err(format!("line {}: `{}` appears before any [[answer]]", line_number, key))
+			};
+
+			match key {
+				"day" => current.day = Some(parse_integer(value, line_number)?),
+				"part" => current.part = Some(parse_integer(value, line_number)?),
+				"expected" => current.expected = Some(parse_value(value, line_number)?),
+				x => return Err(format!("line {}: unknown key `{}`", line_number, x))
+			}
+		}
+
+		if let Some((start, finished)) = entry { insert_entry(&mut expected, start, finished)?; }
+
+		return Ok(Answers { expected });
+	}
+
+	pub fn expected(&self, day: i32, part: i32) -> Option<&str> {
+		return self.expected.get(&(day, part)).map(|answer| answer.as_str());
+	}
+
+	pub fn check(&self, results: &[DayResults]) -> Vec<Check> {
+		let mut checks = vec![];
+
+		for day in results {
+			for part in &day.parts {
+				let expected = self.expected(day.day, part.part);
+				let verdict = match expected {
+					_ if part.status != Status::Solved => Verdict::Fail,
+					None => Verdict::Missing,
+					Some(x) if x == part.answer => Verdict::Pass,
+					Some(_) => Verdict::Fail
+				};
+
+				// A part that didn't produce an answer reports why instead
+				let answer = part.status.error().map(String::from).unwrap_or_else(|| part.answer.clone());
+				checks.push(Check { day: day.day, part: part.part, answer, expected: expected.map(String::from), verdict });
+			}
+		}
+
+		return checks;
+	}
+}
+
+pub fn render(checks: &[Check]) -> String {
+	let mut report = String::new();
+
+	for check in checks {
+		let detail = match (&check.verdict, &check.expected) {
+			(Verdict::Fail, Some(expected)) => format!("got {}, expected {}", check.answer, expected),
+			_ => check.answer.clone()
+		};
+		report += &format!("Day {} part {}: {} ({})\n", check.day, check.part, check.verdict.label(), detail);
+	}
+
+	return report;
+}
+
+fn insert_entry(expected: &mut HashMap<(i32, i32), String>, line_number: usize, entry: Entry) -> Result<(), String> {
+	match entry {
+		Entry { day: Some(day), part: Some(part), expected: Some(answer) } => {
+			if expected.insert((day, part), answer).is_some() {
+				return Err(format!("line {}: day {} part {} is listed twice", line_number, day, part));
+			}
+			return Ok(());
+		},
+		_ => return Err(format!("line {}: [[answer]] needs day, part and expected", line_number))
+	}
+}
+
+fn strip_comment(line: &str) -> &str {
+	let mut in_string = false;
+	for (index, c) in line.char_indices() {
+		match c {
+			'"' => in_string = !in_string,
+			'#' if !in_string => return &line[..index],
+			_ => ()
+		}
+	}
+
+	return line;
+}
+
+fn parse_integer(value: &str, line_number: usize) -> Result<i32, String> {
+	return value.parse::<i32>().map_err(|_| format!("line {}: `{}` is not an integer", line_number, value));
+}
+
+// Answers can be written as integers or strings; both are compared as the text the solver printed
+fn parse_value(value: &str, line_number: usize) -> Result<String, String> {
+	if let Some(inner) = value.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+		return Ok(String::from(inner));
+	}
+
+	if !value.is_empty() && value.trim_start_matches('-').chars().all(|c| c.is_ascii_digit()) {
+		return Ok(String::from(value));
+	}
+
+	return Err(format!("line {}: `{}` is not a string or integer", line_number, value));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::results::PartResult;
+	use std::time::Duration;
+
+	#[test]
+	fn parsing_works() {
+		let answers = Answers::parse("# answers for my input
+[[answer]]
+day = 1
+part = 1
+expected = 514579
+
+[[answer]]
+day = 1   # trailing comments are fine
+part = 2
+expected = \"241861950\"
+").unwrap();
+
+		assert_eq!(answers.expected(1, 1), Some("514579"));
+		assert_eq!(answers.expected(1, 2), Some("241861950"));
+		assert_eq!(answers.expected(2, 1), None);
+	}
+
+	#[test]
+	fn bad_files_are_rejected() {
+		assert!(Answers::parse("day = 1").is_err());
+		assert!(Answers::parse("[[answer]]\nday = 1\npart = 1").is_err());
+		assert!(Answers::parse("[[answer]]\nday = one\npart = 1\nexpected = 2").is_err());
+		assert!(Answers::parse("[[answer]]\nday = 1\npart = 1\nexpected = 2\n[[answer]]\nday = 1\npart = 1\nexpected = 3").is_err());
+	}
+
+	#[test]
+	fn checking_works() {
+		let answers = Answers::parse("[[answer]]\nday = 17\npart = 1\nexpected = 112\n[[answer]]\nday = 17\npart = 2\nexpected = 848").unwrap();
+		let part = |part, answer: &str| PartResult { part, answer: String::from(answer), elapsed: Duration::from_millis(1), status: Status::Solved, cached: false };
+		let results = vec![
+			DayResults { day: 17, parts: vec![part(1, "289"), part(2, "848")] },
+			DayResults { day: 18, parts: vec![part(1, "26")] }
+		];
+
+		let verdicts: Vec<Verdict> = answers.check(&results).iter().map(|check| check.verdict).collect();
+		assert_eq!(verdicts, vec![Verdict::Fail, Verdict::Pass, Verdict::Missing]);
+
+		let failed = vec![DayResults { day: 17, parts: vec![PartResult { status: Status::Failed(String::from("oops")), ..part(2, "") }] }];
+		assert_eq!(render(&answers.check(&failed)), "Day 17 part 2: FAIL (got oops, expected 848)\n");
+		assert_eq!(render(&answers.check(&results)), "Day 17 part 1: FAIL (got 289, expected 112)
+Day 17 part 2: PASS (848)
+Day 18 part 1: MISSING (26)
+");
+	}
+}
\ No newline at end of file
@@ -0,0 +1,160 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	time::Duration
+};
+
+use crate::cache::Cache;
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::results::PartResult;
+use crate::runner;
+use crate::solver::Solver;
+
+// A batch directory holds one subdirectory per person, each with whichever dayN.txt inputs they have
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct User {
+	pub name: String,
+	pub dir: PathBuf
+}
+
+impl User {
+	pub fn input(&self, day: i32) -> Option<PathBuf> {
+		let path = self.dir.join(format!("day{}.txt", day));
+		if path.is_file() { return Some(path); }
+		return None;
+	}
+}
+
+pub fn users(root: &Path) -> Result<Vec<User>> {
+	let entries = fs::read_dir(root).map_err(|e| Error::new(format!("couldn't read {}: {}", root.display(), e)))?;
+	let mut users: Vec<User> = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|path| path.is_dir())
+		.map(|dir| User { name: dir.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(), dir }).collect();
+	users.sort_by(|a, b| a.name.cmp(&b.name));
+
+	if users.is_empty() { return Err(Error::new(format!("{} has no user directories", root.display()))); }
+	return Ok(users);
+}
+
+// One row per day and part, with a cell for each user in the same order as `users`; None is a missing input
+#[derive(Clone, Debug)]
+pub struct Row {
+	pub day: i32,
+	pub part: i32,
+	pub cells: Vec<Option<PartResult>>
+}
+
+#[derive(Clone, Debug)]
+pub struct Matrix {
+	pub users: Vec<String>,
+	pub rows: Vec<Row>
+}
+
+// A solver that panics for some inputs but copes with others usually has an assumption that only some inputs meet
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MixedPanic {
+	pub day: i32,
+	pub part: i32,
+	pub panicked: Vec<String>,
+	pub survived: Vec<String>
+}
+
+impl Matrix {
+	pub fn mixed_panics(&self) -> Vec<MixedPanic> {
+		return self.rows.iter().filter_map(|row| {
+			let (mut panicked, mut survived) = (vec![], vec![]);
+			for (user, cell) in self.users.iter().zip(&row.cells) {
+				match cell {
+					Some(result) if result.status.panicked() => panicked.push(user.clone()),
+					Some(_) => survived.push(user.clone()),
+					None => ()
+				}
+			}
+
+			if panicked.is_empty() || survived.is_empty() { return None; }
+			return Some(MixedPanic { day: row.day, part: row.part, panicked, survived });
+		}).collect();
+	}
+
+	pub fn results(&self) -> impl Iterator<Item = (&String, i32, &PartResult)> {
+		return self.rows.iter().flat_map(move |row| self.users.iter().zip(&row.cells).filter_map(move |(user, cell)| cell.as_ref().map(|result| (user, row.day, result))));
+	}
+}
+
+// Users run one after another, each with the usual number of days at once, so timings stay comparable between them
+pub async fn run(users: &[User], solvers: &[&'static dyn Solver], parts: &[i32], jobs: usize, timeout: Option<Duration>, cache: Option<Cache>, progress: bool) -> Matrix {
+	let mut rows: Vec<Row> = solvers.iter().flat_map(|solver| parts.iter().map(move |part| Row { day: solver.day(), part: *part, cells: vec![] })).collect();
+
+	for user in users {
+		let days = solvers.iter().filter_map(|solver| user.input(solver.day()).map(|path| (*solver, Source::File(path)))).collect();
+		let results = runner::run_days(days, parts, jobs, timeout, cache.clone(), None, progress).await;
+
+		for row in rows.iter_mut() {
+			let found = results.iter().filter(|day| day.day == row.day).flat_map(|day| day.parts.iter()).find(|result| result.part == row.part);
+			row.cells.push(found.cloned());
+		}
+	}
+
+	rows.retain(|row| row.cells.iter().any(|cell| cell.is_some()));
+	return Matrix { users: users.iter().map(|user| user.name.clone()).collect(), rows };
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+	use crate::results::Status;
+	use crate::solver;
+
+	fn temp_dir(name: &str) -> PathBuf {
+		let dir = std::env::temp_dir().join(format!("advent2020-batch-{}-{}", name, std::process::id()));
+		let _ = fs::remove_dir_all(&dir);
+		return dir;
+	}
+
+	fn write(path: PathBuf, text: &str) {
+		fs::create_dir_all(path.parent().unwrap()).unwrap();
+		fs::write(path, text).unwrap();
+	}
+
+	#[tokio::test]
+	async fn every_user_gets_a_column() {
+		let root = temp_dir("run");
+		write(root.join("bob/day1.txt"), "1721\n979\n366\n299\n675\n1456\n");
+		write(root.join("bob/day8.txt"), "nop +0\nhcf +1\n");
+		write(root.join("alice/day1.txt"), "2019\n1\n1000\n1020\n0\n");
+		write(root.join("notes.txt"), "not a user");
+
+		let users = users(&root).unwrap();
+		assert_eq!(users.iter().map(|user| user.name.as_str()).collect::<Vec<_>>(), vec!["alice", "bob"]);
+
+		let solvers = vec![solver::find(1).unwrap(), solver::find(2).unwrap(), solver::find(8).unwrap()];
+		let matrix = run(&users, &solvers, &[1], 2, None, None, false).await;
+
+		assert_eq!(matrix.rows.iter().map(|row| row.day).collect::<Vec<_>>(), vec![1, 8]);
+		assert_eq!(matrix.rows[0].cells[0].as_ref().unwrap().answer, "2019");
+		assert_eq!(matrix.rows[0].cells[1].as_ref().unwrap().answer, "514579");
+		assert!(matrix.rows[1].cells[0].is_none());
+		assert_eq!(matrix.rows[1].cells[1].as_ref().unwrap().status, Status::Failed(String::from("day 8, line 2: unknown operation 'hcf'")));
+		assert_eq!(matrix.results().count(), 3);
+		assert!(matrix.mixed_panics().is_empty());
+
+		fs::remove_dir_all(&root).unwrap();
+		assert!(super::users(&root).is_err());
+	}
+
+	#[test]
+	fn panics_on_only_some_inputs_are_flagged() {
+		let result = |status| Some(PartResult { part: 2, answer: String::new(), elapsed: Duration::default(), status, cached: false });
+		let matrix = Matrix {
+			users: vec![String::from("alice"), String::from("bob"), String::from("carol"), String::from("dave")],
+			rows: vec![
+				Row { day: 10, part: 2, cells: vec![result(Status::Solved), result(Status::Failed(String::from("day 10: panicked: oops"))), None, result(Status::Failed(String::from("day 10: line 1: bad")))] },
+				Row { day: 11, part: 2, cells: vec![result(Status::Failed(String::from("day 11: panicked: oops"))), None, None, None] }
+			]
+		};
+
+		assert_eq!(matrix.mixed_panics(), vec![MixedPanic { day: 10, part: 2, panicked: vec![String::from("bob")], survived: vec![String::from("alice"), String::from("dave")] }]);
+	}
+}
\ No newline at end of file
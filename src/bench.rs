@@ -0,0 +1,207 @@
+use std::{
+	panic::{self, AssertUnwindSafe},
+	time::{Duration, Instant}
+};
+
+use crate::error::{Error, Result};
+use crate::input::{Input, Source};
+use crate::memory::{self, Usage};
+use crate::results::PANICKED;
+use crate::runner;
+use crate::solver::Solver;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+	pub min: Duration,
+	pub median: Duration,
+	pub max: Duration
+}
+
+impl Stats {
+	pub fn new(samples: &[Duration]) -> Stats {
+		let mut sorted = samples.to_vec();
+		sorted.sort_unstable();
+
+		let middle = sorted.len() / 2;
+		let median = if sorted.len().is_multiple_of(2) { (sorted[middle - 1] + sorted[middle]) / 2 } else { sorted[middle] };
+
+		return Stats { min: sorted[0], median, max: sorted[sorted.len() - 1] };
+	}
+}
+
+pub struct BenchResult {
+	pub day: i32,
+	pub iterations: usize,
+	pub parse: Stats,
+	pub parse_memory: Option<Usage>,
+	pub parts: Vec<PartBench>
+}
+
+// A part that fails stops being run, and its stats only cover the runs up to and including the failure
+pub struct PartBench {
+	pub part: i32,
+	pub stats: Stats,
+	pub memory: Option<Usage>,
+	pub error: Option<String>
+}
+
+impl BenchResult {
+	pub fn any_failed(&self) -> bool {
+		return self.parts.iter().any(|part| part.error.is_some());
+	}
+}
+
+// The source is only read once, since stdin can't be replayed, but each iteration parses it afresh and that
+// parsing is timed separately from the solvers. With the memory feature each stage also reports the most it
+// allocated in any one iteration
+pub fn bench_day(solver: &dyn Solver, parts: &[i32], source: &Source, iterations: usize) -> Result<BenchResult> {
+	let iterations = iterations.max(1);
+	let text = source.read()?;
+	let mut parse_samples = vec![];
+	let mut parse_memory = None;
+	let mut part_samples = vec![vec![]; parts.len()];
+	let mut part_memory = vec![None; parts.len()];
+	let mut part_errors: Vec<Option<String>> = vec![None; parts.len()];
+
+	for _ in 0..iterations {
+		let start = Instant::now();
+		let (data, usage) = memory::measure(|| Input::parse(solver.input_kind(), text.clone()));
+		parse_samples.push(start.elapsed());
+		parse_memory = most(parse_memory, usage);
+		let data = data?;
+
+		for (index, part) in parts.iter().enumerate() {
+			if part_errors[index].is_some() { continue; }
+
+			let start = Instant::now();
+			let (answer, usage) = memory::measure(|| run_part(solver, *part, &data));
+			part_samples[index].push(start.elapsed());
+			part_memory[index] = most(part_memory[index], usage);
+			if let Err(e) = answer { part_errors[index] = Some(e.with_day(solver.day()).to_string()); }
+		}
+	}
+
+	let part_benches = parts.iter().zip(part_samples).zip(part_memory).zip(part_errors)
+		.map(|(((part, samples), memory), error)| PartBench { part: *part, stats: Stats::new(&samples), memory, error }).collect();
+	return Ok(BenchResult { day: solver.day(), iterations, parse: Stats::new(&parse_samples), parse_memory, parts: part_benches });
+}
+
+// Panics are caught the same way the runner catches them, so one bad part doesn't end the whole benchmark
+fn run_part(solver: &dyn Solver, part: i32, data: &Input) -> Result<String> {
+	match panic::catch_unwind(AssertUnwindSafe(|| solver.part(part, data))) {
+		Ok(result) => return result,
+		Err(payload) => return Err(Error::new(format!("{}{}", PANICKED, runner::panic_message(payload.as_ref()))))
+	}
+}
+
+fn most(a: Option<Usage>, b: Option<Usage>) -> Option<Usage> {
+	match (a, b) {
+		(Some(a), Some(b)) => return Some(a.max(b)),
+		(a, b) => return a.or(b)
+	}
+}
+
+// The memory columns only appear when something was measured, which needs the memory feature
+pub fn render(results: &[BenchResult]) -> String {
+	let with_memory = results.iter().any(|result| result.parse_memory.is_some());
+	let mut table = format!("{:>4} {:>6} {:>12} {:>12} {:>12} {:>6}", "day", "stage", "min", "median", "max", "runs");
+	if with_memory { table += &format!(" {:>10} {:>10}", "peak", "allocated"); }
+	table.push('\n');
+
+	for result in results {
+		table += &row(result.day, "parse", &result.parse, result.iterations, result.parse_memory);
+		for part in &result.parts {
+			match &part.error {
+				Some(error) => table += &format!("{:>4} {:>6} FAILED: {}\n", result.day, format!("part{}", part.part), error),
+				None => table += &row(result.day, &format!("part{}", part.part), &part.stats, result.iterations, part.memory)
+			}
+		}
+	}
+
+	return table;
+}
+
+fn row(day: i32, stage: &str, stats: &Stats, iterations: usize, usage: Option<Usage>) -> String {
+	let mut row = format!("{:>4} {:>6} {:>12} {:>12} {:>12} {:>6}", day, stage, millis(stats.min), millis(stats.median), millis(stats.max), iterations);
+	if let Some(usage) = usage { row += &format!(" {:>10} {:>10}", bytes(usage.peak), bytes(usage.total)); }
+	row.push('\n');
+
+	return row;
+}
+
+fn bytes(count: u64) -> String {
+	let units = ["B", "KiB", "MiB", "GiB"];
+	let mut size = count as f64;
+	let mut unit = 0;
+	while size >= 1024.0 && unit < units.len() - 1 {
+		size /= 1024.0;
+		unit += 1;
+	}
+
+	if unit == 0 { return format!("{}B", count); }
+	return format!("{:.1}{}", size, units[unit]);
+}
+
+fn millis(duration: Duration) -> String {
+	return format!("{:.3}ms", duration.as_secs_f64() * 1000.0);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::input::InputKind;
+
+	#[test]
+	fn stats_work() {
+		let ms = Duration::from_millis;
+		assert_eq!(Stats::new(&[ms(5), ms(1), ms(3)]), Stats { min: ms(1), median: ms(3), max: ms(5) });
+		assert_eq!(Stats::new(&[ms(4), ms(1), ms(2), ms(8)]), Stats { min: ms(1), median: ms(3), max: ms(8) });
+		assert_eq!(Stats::new(&[ms(7)]), Stats { min: ms(7), median: ms(7), max: ms(7) });
+	}
+
+	#[test]
+	fn render_works() {
+		let ms = Duration::from_millis;
+		let stats = Stats { min: ms(1), median: ms(2), max: ms(3) };
+		let results = vec![BenchResult { day: 15, iterations: 3, parse: stats, parse_memory: None, parts: vec![PartBench { part: 2, stats, memory: None, error: None }] }];
+
+		assert_eq!(render(&results), " day  stage          min       median          max   runs
+  15  parse      1.000ms      2.000ms      3.000ms      3
+  15  part2      1.000ms      2.000ms      3.000ms      3
+");
+	}
+
+	#[test]
+	fn render_shows_memory_when_measured() {
+		let ms = Duration::from_millis;
+		let stats = Stats { min: ms(1), median: ms(2), max: ms(3) };
+		let usage = |peak, total| Some(Usage { peak, total });
+		let results = vec![BenchResult { day: 14, iterations: 1, parse: stats, parse_memory: usage(512, 512), parts: vec![PartBench { part: 2, stats, memory: usage(3 << 20, 5 << 30), error: None }] }];
+
+		assert_eq!(render(&results), " day  stage          min       median          max   runs       peak  allocated
+  14  parse      1.000ms      2.000ms      3.000ms      1       512B       512B
+  14  part2      1.000ms      2.000ms      3.000ms      1     3.0MiB     5.0GiB
+");
+	}
+
+	struct Flaky;
+
+	impl Solver for Flaky {
+		fn day(&self) -> i32 { 99 }
+		fn name(&self) -> &str { "Flaky" }
+		fn input_kind(&self) -> InputKind { InputKind::Chunk }
+		fn version(&self) -> &str { "1" }
+		fn part1(&self, _: &Input) -> Result<String> { panic!("not today") }
+		fn part2(&self, input: &Input) -> Result<String> { Ok(input.chunk().len().to_string()) }
+	}
+
+	#[test]
+	fn failing_parts_are_recorded_and_the_rest_still_run() {
+		let result = bench_day(&Flaky, &[1, 2], &Source::parse("data/day15.txt"), 3).unwrap();
+		assert!(result.any_failed());
+
+		assert_eq!(result.parts[0].error.as_deref(), Some("day 99: panicked: not today"));
+		assert_eq!(result.parts[1].error, None);
+		assert!(render(&[result]).contains("  99  part1 FAILED: day 99: panicked: not today\n"));
+	}
+}
\ No newline at end of file
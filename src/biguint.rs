@@ -0,0 +1,228 @@
+use std::{
+	cmp::Ordering,
+	fmt,
+	iter::Sum,
+	ops::{Add, AddAssign, Mul},
+	str::FromStr
+};
+
+use crate::error::{Error, Result};
+
+// An unsigned integer of any size, for answers that can outgrow even u128. It only does what answers need: adding,
+// multiplying, comparing, parsing and printing in decimal
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+	// Base 2^32 digits, least significant first, with no zero digits at the top, so zero has none at all
+	limbs: Vec<u32>
+}
+
+// Printing goes through the biggest power of ten that fits in a limb, nine decimal digits at a time
+const DECIMAL_CHUNK: u64 = 1_000_000_000;
+
+impl BigUint {
+	pub fn zero() -> BigUint {
+		return BigUint::default();
+	}
+
+	pub fn is_zero(&self) -> bool {
+		return self.limbs.is_empty();
+	}
+
+	pub fn to_u128(&self) -> Option<u128> {
+		if self.limbs.len() > 4 { return None; }
+		return Some(self.limbs.iter().rev().fold(0, |value, limb| value << 32 | *limb as u128));
+	}
+
+	fn trim(mut self) -> BigUint {
+		while self.limbs.last() == Some(&0) {
+			self.limbs.pop();
+		}
+
+		return self;
+	}
+
+	// Multiplies in place by a single digit and adds a carry, which is all parsing needs
+	fn mul_add_small(&mut self, factor: u32, carry: u32) {
+		let mut carry = carry as u64;
+		for limb in self.limbs.iter_mut() {
+			let value = *limb as u64 * factor as u64 + carry;
+			*limb = value as u32;
+			carry = value >> 32;
+		}
+		if carry > 0 { self.limbs.push(carry as u32); }
+	}
+
+	// Divides in place by a single digit, returning the remainder
+	fn div_rem_small(&mut self, divisor: u32) -> u32 {
+		let mut remainder = 0u64;
+		for limb in self.limbs.iter_mut().rev() {
+			let value = remainder << 32 | *limb as u64;
+			*limb = (value / divisor as u64) as u32;
+			remainder = value % divisor as u64;
+		}
+
+		let trimmed = std::mem::take(self).trim();
+		*self = trimmed;
+		return remainder as u32;
+	}
+}
+
+impl From<u128> for BigUint {
+	fn from(mut value: u128) -> BigUint {
+		let mut limbs = vec![];
+		while value > 0 {
+			limbs.push(value as u32);
+			value >>= 32;
+		}
+
+		return BigUint { limbs };
+	}
+}
+
+impl From<u64> for BigUint {
+	fn from(value: u64) -> BigUint {
+		return BigUint::from(value as u128);
+	}
+}
+
+impl<'a> Add<&'a BigUint> for &'a BigUint {
+	type Output = BigUint;
+
+	fn add(self, other: &BigUint) -> BigUint {
+		let (long, short) = if self.limbs.len() >= other.limbs.len() { (self, other) } else { (other, self) };
+
+		let mut limbs = Vec::with_capacity(long.limbs.len() + 1);
+		let mut carry = 0u64;
+		for (index, limb) in long.limbs.iter().enumerate() {
+			let value = *limb as u64 + *short.limbs.get(index).unwrap_or(&0) as u64 + carry;
+			limbs.push(value as u32);
+			carry = value >> 32;
+		}
+		if carry > 0 { limbs.push(carry as u32); }
+
+		return BigUint { limbs };
+	}
+}
+
+impl AddAssign<&BigUint> for BigUint {
+	fn add_assign(&mut self, other: &BigUint) {
+		*self = &*self + other;
+	}
+}
+
+impl<'a> Mul<&'a BigUint> for &'a BigUint {
+	type Output = BigUint;
+
+	// Schoolbook multiplication, which is plenty for numbers a few hundred bits long
+	fn mul(self, other: &BigUint) -> BigUint {
+		if self.is_zero() || other.is_zero() { return BigUint::zero(); }
+
+		let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+		for (i, a) in self.limbs.iter().enumerate() {
+			let mut carry = 0u64;
+			for (j, b) in other.limbs.iter().enumerate() {
+				let value = *a as u64 * *b as u64 + limbs[i + j] as u64 + carry;
+				limbs[i + j] = value as u32;
+				carry = value >> 32;
+			}
+			limbs[i + other.limbs.len()] = carry as u32;
+		}
+
+		return BigUint { limbs }.trim();
+	}
+}
+
+impl<'a> Sum<&'a BigUint> for BigUint {
+	fn sum<I>(values: I) -> BigUint where I: Iterator<Item = &'a BigUint> {
+		return values.fold(BigUint::zero(), |total, value| &total + value);
+	}
+}
+
+impl Ord for BigUint {
+	fn cmp(&self, other: &BigUint) -> Ordering {
+		return self.limbs.len().cmp(&other.limbs.len()).then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()));
+	}
+}
+
+impl PartialOrd for BigUint {
+	fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+		return Some(self.cmp(other));
+	}
+}
+
+impl fmt::Display for BigUint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.is_zero() { return f.pad("0"); }
+
+		let mut rest = self.clone();
+		let mut chunks = vec![];
+		while !rest.is_zero() {
+			chunks.push(rest.div_rem_small(DECIMAL_CHUNK as u32));
+		}
+
+		let mut text = chunks.pop().unwrap_or(0).to_string();
+		for chunk in chunks.iter().rev() {
+			text += &format!("{:09}", chunk);
+		}
+
+		return f.pad(&text);
+	}
+}
+
+impl FromStr for BigUint {
+	type Err = Error;
+
+	fn from_str(text: &str) -> Result<BigUint> {
+		if text.is_empty() { return Err(Error::new("'' is not a valid number")); }
+
+		let mut value = BigUint::zero();
+		for c in text.chars() {
+			let digit = c.to_digit(10).ok_or_else(|| Error::new(format!("'{}' is not a valid number", text)))?;
+			value.mul_add_small(10, digit);
+		}
+
+		return Ok(value.trim());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn big(text: &str) -> BigUint {
+		return text.parse().unwrap();
+	}
+
+	#[test]
+	fn round_trips_through_decimal() {
+		assert_eq!(BigUint::zero().to_string(), "0");
+		assert_eq!(big("000").to_string(), "0");
+		assert_eq!(big("1000000000").to_string(), "1000000000");
+		assert_eq!(BigUint::from(u128::MAX).to_string(), u128::MAX.to_string());
+		assert_eq!(big("123456789012345678901234567890123456789012345678901234567890").to_string(), "123456789012345678901234567890123456789012345678901234567890");
+		assert!("12a".parse::<BigUint>().is_err());
+		assert!("".parse::<BigUint>().is_err());
+	}
+
+	#[test]
+	fn arithmetic_carries_past_u128() {
+		let max = BigUint::from(u128::MAX);
+		assert_eq!((&max + &BigUint::from(1u64)).to_string(), "340282366920938463463374607431768211456");
+		assert_eq!((&max * &max).to_string(), "115792089237316195423570985008687907852589419931798687112530834793049593217025");
+		assert_eq!(&max * &BigUint::zero(), BigUint::zero());
+
+		let mut total = BigUint::from(7u64);
+		total += &BigUint::from(5u64);
+		assert_eq!(total.to_u128(), Some(12));
+		assert_eq!((&max + &max).to_u128(), None);
+		assert_eq!([max.clone(), max.clone()].iter().sum::<BigUint>(), &max + &max);
+	}
+
+	#[test]
+	fn ordering_is_numeric() {
+		assert!(big("99") < big("100"));
+		assert!(BigUint::from(u128::MAX) < &BigUint::from(u128::MAX) + &BigUint::from(1u64));
+		assert!(big("4294967296") > big("4294967295"));
+		assert_eq!(format!("{:>5}", big("42")), "   42");
+	}
+}
\ No newline at end of file
@@ -0,0 +1,109 @@
+use std::{
+	fs,
+	path::PathBuf,
+	sync::atomic::{AtomicUsize, Ordering},
+	time::Duration
+};
+
+use crate::error::{Error, Result};
+
+pub const DEFAULT_DIR: &str = ".cache/advent2020";
+
+// Numbers the temporary files, so days being cached at the same time never write to the same one
+static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+// FNV-1a is plenty to tell inputs apart and doesn't need a dependency
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+	let mut hash: u64 = 0xcbf29ce484222325;
+	for byte in bytes {
+		hash ^= *byte as u64;
+		hash = hash.wrapping_mul(0x100000001b3);
+	}
+
+	return hash;
+}
+
+// Answers are stored one file per day, part and key, so switching between several people's inputs doesn't
+// throw away what was cached for the others
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cache {
+	dir: PathBuf
+}
+
+impl Cache {
+	pub fn new(dir: impl Into<PathBuf>) -> Cache {
+		return Cache { dir: dir.into() };
+	}
+
+	pub fn key(version: &str, text: &str) -> String {
+		let mut bytes = Vec::with_capacity(version.len() + text.len() + 1);
+		bytes.extend_from_slice(version.as_bytes());
+		bytes.push(0);
+		bytes.extend_from_slice(text.as_bytes());
+
+		return format!("{:016x}", fnv1a(&bytes));
+	}
+
+	fn path(&self, day: i32, part: i32, key: &str) -> PathBuf {
+		return self.dir.join(format!("day{}-part{}-{}.txt", day, part, key));
+	}
+
+	// Anything unreadable is treated as a miss, the part just gets solved again
+	pub fn get(&self, day: i32, part: i32, key: &str) -> Option<(String, Duration)> {
+		let text = fs::read_to_string(self.path(day, part, key)).ok()?;
+		let (nanos, answer) = text.split_once('\n')?;
+
+		return Some((String::from(answer), Duration::from_nanos(nanos.parse().ok()?)));
+	}
+
+	// The entry is written to a temporary file and renamed into place, so a run that's interrupted, or racing another
+	// run, can't leave behind a half written entry that reads back as an answer
+	pub fn put(&self, day: i32, part: i32, key: &str, answer: &str, elapsed: Duration) -> Result<()> {
+		let path = self.path(day, part, key);
+		let temporary = path.with_extension(format!("{}-{}.tmp", std::process::id(), WRITES.fetch_add(1, Ordering::Relaxed)));
+		let contents = format!("{}\n{}", elapsed.as_nanos(), answer);
+
+		let written = fs::create_dir_all(&self.dir).and_then(|_| fs::write(&temporary, contents)).and_then(|_| fs::rename(&temporary, &path));
+		if written.is_err() { let _ = fs::remove_file(&temporary); }
+
+		return written.map_err(|e| Error::new(format!("couldn't write {}: {}", path.display(), e)));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fnv1a_matches_the_reference_values() {
+		assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+		assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+		assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+	}
+
+	#[test]
+	fn keys_cover_the_version_and_the_input() {
+		assert_eq!(Cache::key("1", "0,3,6"), Cache::key("1", "0,3,6"));
+		assert_ne!(Cache::key("1", "0,3,6"), Cache::key("2", "0,3,6"));
+		assert_ne!(Cache::key("1", "0,3,6"), Cache::key("1", "0,3,7"));
+		assert_ne!(Cache::key("1", "0,3,6"), Cache::key("10", ",3,6"));
+	}
+
+	#[test]
+	fn answers_round_trip() {
+		let dir = std::env::temp_dir().join(format!("advent2020-cache-{}", std::process::id()));
+		let cache = Cache::new(&dir);
+		let key = Cache::key("1", "input");
+
+		assert_eq!(cache.get(15, 2, &key), None);
+		cache.put(15, 2, &key, "955", Duration::from_millis(3200)).unwrap();
+		assert_eq!(cache.get(15, 2, &key), Some((String::from("955"), Duration::from_millis(3200))));
+		assert_eq!(cache.get(15, 1, &key), None);
+
+		cache.put(15, 2, &key, "956", Duration::from_millis(10)).unwrap();
+		assert_eq!(cache.get(15, 2, &key), Some((String::from("956"), Duration::from_millis(10))));
+		assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+		fs::remove_dir_all(&dir).unwrap();
+	}
+}
\ No newline at end of file
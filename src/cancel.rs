@@ -0,0 +1,67 @@
+use std::{
+	cell::RefCell,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc
+	}
+};
+
+use crate::error::{Error, Result};
+
+// The runner hands a token to each day's thread; a solver stuck in a long loop notices it being cancelled the next
+// time it calls check(), since a blocking thread can't be stopped from the outside
+#[derive(Clone, Debug, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+	pub fn new() -> Token {
+		return Token::default();
+	}
+
+	pub fn cancel(&self) {
+		self.0.store(true, Ordering::Relaxed);
+	}
+
+	pub fn is_cancelled(&self) -> bool {
+		return self.0.load(Ordering::Relaxed);
+	}
+}
+
+thread_local! {
+	static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+pub fn with_token<T, F>(token: &Token, f: F) -> T where F: FnOnce() -> T {
+	let previous = CURRENT.with(|current| current.replace(Some(token.clone())));
+	let result = f();
+	CURRENT.with(|current| *current.borrow_mut() = previous);
+
+	return result;
+}
+
+// Outside the runner there is no token and nothing can be cancelled
+pub fn check() -> Result<()> {
+	let cancelled = CURRENT.with(|current| current.borrow().as_ref().is_some_and(|token| token.is_cancelled()));
+	if cancelled { return Err(Error::new("cancelled")); }
+
+	return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn checks_follow_the_current_token() {
+		let token = Token::new();
+		assert!(check().is_ok());
+
+		with_token(&token, || {
+			assert!(check().is_ok());
+			token.cancel();
+			assert_eq!(check().unwrap_err().message, "cancelled");
+		});
+
+		assert!(check().is_ok());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use crate::error::{Error, Result};
+
+// The integer types answers come in. Release builds wrap on overflow, which turns a big input into a wrong answer
+// with no warning, so answer arithmetic goes through these and overflow comes back as an error instead
+pub trait Integer: Copy {
+	const NAME: &'static str;
+	const ZERO: Self;
+	const ONE: Self;
+
+	fn add(self, other: Self) -> Option<Self>;
+	fn sub(self, other: Self) -> Option<Self>;
+	fn mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! integer {
+	($($type:ident),*) => {
+		$(impl Integer for $type {
+			const NAME: &'static str = stringify!($type);
+			const ZERO: Self = 0;
+			const ONE: Self = 1;
+
+			fn add(self, other: Self) -> Option<Self> { self.checked_add(other) }
+			fn sub(self, other: Self) -> Option<Self> { self.checked_sub(other) }
+			fn mul(self, other: Self) -> Option<Self> { self.checked_mul(other) }
+		})*
+	}
+}
+
+integer!(i32, i64, i128, u32, u64, u128, usize);
+
+// `what` names the value being worked out, e.g. "the error rate", so the message says which answer didn't fit
+pub fn overflow<T: Integer>(what: &str) -> Error {
+	return Error::new(format!("{} overflows {}", what, T::NAME));
+}
+
+pub fn add<T: Integer>(a: T, b: T, what: &str) -> Result<T> {
+	return a.add(b).ok_or_else(|| overflow::<T>(what));
+}
+
+pub fn sub<T: Integer>(a: T, b: T, what: &str) -> Result<T> {
+	return a.sub(b).ok_or_else(|| overflow::<T>(what));
+}
+
+pub fn mul<T: Integer>(a: T, b: T, what: &str) -> Result<T> {
+	return a.mul(b).ok_or_else(|| overflow::<T>(what));
+}
+
+pub fn sum<T: Integer, I>(values: I, what: &str) -> Result<T> where I: IntoIterator<Item = T> {
+	return values.into_iter().try_fold(T::ZERO, |total, value| add(total, value, what));
+}
+
+pub fn product<T: Integer, I>(values: I, what: &str) -> Result<T> where I: IntoIterator<Item = T> {
+	return values.into_iter().try_fold(T::ONE, |total, value| mul(total, value, what));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn overflow_is_an_error() {
+		assert_eq!(add(2i32, 3, "x").unwrap(), 5);
+		assert_eq!(add(i32::MAX, 1, "the total").unwrap_err().message, "the total overflows i32");
+		assert_eq!(sub(0usize, 1, "the gap").unwrap_err().message, "the gap overflows usize");
+		assert_eq!(mul(u128::MAX, 2, "the product").unwrap_err().message, "the product overflows u128");
+	}
+
+	#[test]
+	fn sums_and_products_fold() {
+		assert_eq!(sum(vec![1i64, 2, 3], "x").unwrap(), 6);
+		assert_eq!(sum(Vec::<i64>::new(), "x").unwrap(), 0);
+		assert_eq!(product(vec![4usize, 5], "x").unwrap(), 20);
+		assert_eq!(product(Vec::<usize>::new(), "x").unwrap(), 1);
+		assert!(product(vec![1usize << 40, 1 << 40], "x").is_err());
+		assert!(sum(vec![i64::MAX, 1, -5], "x").is_err());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,593 @@
+use std::{
+	path::PathBuf,
+	time::Duration
+};
+
+use advent2020::generate;
+use advent2020::input::{InputKind, Source};
+use advent2020::visualize::Target;
+use crate::output::Format;
+use crate::report;
+
+pub const USAGE: &str = "usage: advent2020 [--all] [--day N] [--days A-B[,C...]] [--part P] [--format FORMAT]
+                  [--input [N=]PATH] [--data-dir DIR] [--jobs N | --sequential] [--timeout SECS] [--watch] [--no-cache]
+                  [--visualize | --visualize-to DIR] [--bench [--iterations N]] [--check [--answers PATH]] [--batch DIR]
+                  [--report PATH]
+
+  --all           run every day (the default when no days are given)
+  --day N         run a single day; may be repeated
+  --days LIST     run a comma separated list of days and ranges, e.g. 1,3,10-14
+  --part P        only run part 1 or part 2 of the selected days
+  --format FORMAT print results as text (the default), json or csv
+  --input PATH    read the selected day's input from PATH, or from stdin when PATH is -
+  --input N=PATH  read day N's input from PATH; may be repeated
+  --data-dir DIR  read dayN.txt files from DIR instead of data (AOC_DATA_DIR also sets this)
+  --jobs N        run at most N days at once (default: one per CPU)
+  --sequential    run one day at a time, the same as --jobs 1
+  --timeout SECS  give up on a day that hasn't finished after SECS seconds and report it as timed out
+  --no-cache      solve everything even if .cache/advent2020 has answers for the same input and solver version
+  --watch         rerun a day whenever its input or example files change, until interrupted
+  --visualize     draw each step of days 3, 11, 12 and 17 in the terminal; runs one day at a time and skips the cache
+  --visualize-to DIR
+                  write each step as a numbered PPM image in DIR instead, e.g. DIR/day11-part1-00001.ppm
+  --bench         time each selected part repeatedly and report min/median/max; built with --features memory it
+                  also reports the peak and total bytes each part allocates
+  --iterations N  how many runs --bench takes per part (default 10)
+  --check         compare answers against the answers file and exit non-zero on a mismatch
+  --answers PATH  the answers file --check reads (default answers.toml)
+  --report PATH   also write the run as a Markdown (.md) or HTML (.html) report, marking each answer as passing or
+                  failing against the answers file if there is one; may be repeated
+  --batch DIR     run the selected days against every user's inputs in DIR/USER/dayN.txt and print a table with a
+                  column per user, flagging parts that panic for some users but not others
+  --list          list the registered days and exit
+  -h, --help      print this message
+
+usage: advent2020 new-day N [--input lines|chunk|numbers|records] [--name NAME]
+
+  Creates src/dayN.rs with part1/part2 stubs and an example test, registers it in src/lib.rs and
+  src/solver.rs, and creates an empty data/dayN.txt. Run it from the repository root.
+  --input KIND    how the day's input is handed to the solver (default lines)
+  --name NAME     the puzzle's title, shown by --list (default \"Day N\")
+
+usage: advent2020 generate N [--size S] [--seed X]
+
+  Prints a random but valid input for day N, one of 2, 5, 7, 8, 14, 16 or 18. The same seed gives the
+  same input.
+  --size S        roughly how many lines, records or instructions to generate (default 100)
+  --seed X        the random seed (default: taken from the clock)
+
+usage: advent2020 serve [--host HOST] [--port N] [--jobs N] [--timeout SECS]
+
+  Answers puzzles over HTTP until interrupted. POST /day/N/part/P with the puzzle input as the body returns
+  the answer, timing and any error as JSON; GET /days lists the days that can be solved.
+  --host HOST     the address to listen on (default 127.0.0.1)
+  --port N        the port to listen on (default 2020)
+  --jobs N        solve at most N requests at once, queueing the rest (default: one per CPU)
+  --timeout SECS  give up on a request that hasn't been answered after SECS seconds";
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+	pub days: Vec<i32>,
+	pub parts: Vec<i32>,
+	pub format: Format,
+	pub inputs: Vec<(i32, Source)>,
+	pub data_dir: PathBuf,
+	pub jobs: usize,
+	pub timeout: Option<Duration>,
+	pub watch: bool,
+	pub cache: bool,
+	pub visualize: Option<Target>,
+	pub bench: bool,
+	pub iterations: usize,
+	pub check: bool,
+	pub answers: String,
+	pub reports: Vec<(report::Kind, PathBuf)>,
+	pub batch: Option<PathBuf>,
+	pub list: bool,
+	pub help: bool
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NewDay {
+	pub day: i32,
+	pub kind: InputKind,
+	pub name: String
+}
+
+impl Options {
+	// An explicit --input wins, otherwise the day is looked up in the data directory
+	pub fn source(&self, day: i32) -> Source {
+		match self.inputs.iter().find(|(input_day, _)| *input_day == day) {
+			Some((_, source)) => return source.clone(),
+			None => return Source::File(self.data_dir.join(format!("day{}.txt", day)))
+		}
+	}
+}
+
+// `data_dir` is the fallback from the environment; --data-dir overrides it
+pub fn parse_args<I>(args: I, available: &[i32], data_dir: Option<String>) -> Result<Options, String> where I: IntoIterator<Item = String> {
+	let mut days = vec![];
+	let mut parts = vec![];
+	let mut format = Format::Text;
+	let mut inputs = vec![];
+	let mut data_dir = PathBuf::from(data_dir.unwrap_or_else(|| String::from("data")));
+	let mut jobs = std::thread::available_parallelism().map_or(1, |count| count.get());
+	let mut timeout = None;
+	let mut watch = false;
+	let mut cache = true;
+	let mut visualize = None;
+	let mut bench = false;
+	let mut iterations = 10;
+	let mut check = false;
+	let mut answers = String::from("answers.toml");
+	let mut reports = vec![];
+	let mut batch = None;
+	let mut all = false;
+	let mut list = false;
+	let mut help = false;
+
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--all" => all = true,
+			"--day" => days.push(parse_number(&next_value(&mut args, &arg)?)?),
+			"--days" => days.extend(parse_day_list(&next_value(&mut args, &arg)?)?),
+			"--part" => parts.push(parse_part(&next_value(&mut args, &arg)?)?),
+			"--format" => format = parse_format(&next_value(&mut args, &arg)?)?,
+			"--input" => inputs.push(parse_input(&next_value(&mut args, &arg)?)?),
+			"--data-dir" => data_dir = PathBuf::from(next_value(&mut args, &arg)?),
+			"--jobs" => jobs = parse_jobs(&next_value(&mut args, &arg)?)?,
+			"--sequential" => jobs = 1,
+			"--timeout" => timeout = Some(parse_timeout(&next_value(&mut args, &arg)?)?),
+			"--watch" => watch = true,
+			"--no-cache" => cache = false,
+			"--visualize" => visualize = Some(Target::Terminal),
+			"--visualize-to" => visualize = Some(Target::Images(PathBuf::from(next_value(&mut args, &arg)?))),
+			"--bench" => bench = true,
+			"--iterations" => iterations = parse_iterations(&next_value(&mut args, &arg)?)?,
+			"--check" => check = true,
+			"--answers" => answers = next_value(&mut args, &arg)?,
+			"--report" => reports.push(parse_report(&next_value(&mut args, &arg)?)?),
+			"--batch" => batch = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+			"--list" => list = true,
+			"-h" | "--help" => help = true,
+			x => return Err(format!("unexpected argument '{}'", x))
+		}
+	}
+
+	if all || days.is_empty() {
+		days = available.to_vec();
+	}
+
+	if let Some(day) = days.iter().find(|day| !available.contains(day)) {
+		return Err(format!("day {} is not implemented", day));
+	}
+
+	if parts.is_empty() {
+		parts = vec![1, 2];
+	}
+
+	days.sort_unstable();
+	days.dedup();
+	parts.sort_unstable();
+	parts.dedup();
+
+	let inputs = resolve_inputs(inputs, &days)?;
+
+	if watch && (bench || check) {
+		return Err(String::from("--watch can't be combined with --bench or --check"));
+	}
+	if watch && inputs.iter().any(|(_, source)| *source == Source::Stdin) {
+		return Err(String::from("--watch needs input files, it can't watch stdin"));
+	}
+	if visualize.is_some() && (bench || watch) {
+		return Err(String::from("--visualize can't be combined with --bench or --watch"));
+	}
+
+	if !reports.is_empty() && (watch || bench || batch.is_some()) {
+		return Err(String::from("--report can't be combined with --watch, --bench or --batch"));
+	}
+	if batch.is_some() && (!inputs.is_empty() || watch || bench || check || visualize.is_some()) {
+		return Err(String::from("--batch can't be combined with --input, --watch, --bench, --check or --visualize"));
+	}
+
+	// Cached parts never run, so they'd have nothing to draw, and frames from days running side by side would draw
+	// over each other in the terminal
+	if visualize.is_some() {
+		cache = false;
+	}
+	if visualize == Some(Target::Terminal) {
+		jobs = 1;
+	}
+
+	return Ok(Options { days, parts, format, inputs, data_dir, jobs, timeout, watch, cache, visualize, bench, iterations, check, answers, reports, batch, list, help });
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Generate {
+	pub day: i32,
+	pub size: usize,
+	pub seed: Option<u64>
+}
+
+// The arguments after `generate`
+pub fn parse_generate<I>(args: I) -> Result<Generate, String> where I: IntoIterator<Item = String> {
+	let mut day = None;
+	let mut size = 100;
+	let mut seed = None;
+
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--size" => {
+				let value = next_value(&mut args, &arg)?;
+				size = value.trim().parse::<usize>().map_err(|_| format!("'{}' is not a size", value))?;
+			},
+			"--seed" => {
+				let value = next_value(&mut args, &arg)?;
+				seed = Some(value.trim().parse::<u64>().map_err(|_| format!("'{}' is not a seed", value))?);
+			},
+			x if day.is_none() && !x.starts_with('-') => day = Some(parse_number(x)?),
+			x => return Err(format!("unexpected argument '{}'", x))
+		}
+	}
+
+	let day = day.ok_or_else(|| String::from("generate needs a day number"))?;
+	if !generate::DAYS.contains(&day) {
+		return Err(format!("no generator for day {}, only for days {}", day, generate::DAYS.iter().map(|day| day.to_string()).collect::<Vec<_>>().join(", ")));
+	}
+
+	return Ok(Generate { day, size, seed });
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Serve {
+	pub address: String,
+	pub jobs: usize,
+	pub timeout: Option<Duration>
+}
+
+// The arguments after `serve`
+pub fn parse_serve<I>(args: I) -> Result<Serve, String> where I: IntoIterator<Item = String> {
+	let mut host = String::from("127.0.0.1");
+	let mut port = 2020;
+	let mut jobs = std::thread::available_parallelism().map_or(1, |count| count.get());
+	let mut timeout = None;
+
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--host" => host = next_value(&mut args, &arg)?,
+			"--port" => {
+				let value = next_value(&mut args, &arg)?;
+				port = value.trim().parse::<u16>().map_err(|_| format!("'{}' is not a port", value))?;
+			},
+			"--jobs" => jobs = parse_jobs(&next_value(&mut args, &arg)?)?,
+			"--timeout" => timeout = Some(parse_timeout(&next_value(&mut args, &arg)?)?),
+			x => return Err(format!("unexpected argument '{}'", x))
+		}
+	}
+
+	return Ok(Serve { address: format!("{}:{}", host, port), jobs, timeout });
+}
+
+// The arguments after `new-day`
+pub fn parse_new_day<I>(args: I) -> Result<NewDay, String> where I: IntoIterator<Item = String> {
+	let mut day = None;
+	let mut kind = InputKind::Lines;
+	let mut name = None;
+
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--input" => kind = parse_kind(&next_value(&mut args, &arg)?)?,
+			"--name" => name = Some(next_value(&mut args, &arg)?),
+			x if day.is_none() && !x.starts_with('-') => day = Some(parse_number(x)?),
+			x => return Err(format!("unexpected argument '{}'", x))
+		}
+	}
+
+	let day = day.ok_or_else(|| String::from("new-day needs a day number"))?;
+	if !(1..=25).contains(&day) {
+		return Err(format!("day {} isn't an advent day", day));
+	}
+
+	let name = name.unwrap_or_else(|| format!("Day {}", day));
+	return Ok(NewDay { day, kind, name });
+}
+
+fn parse_kind(value: &str) -> Result<InputKind, String> {
+	match value.trim() {
+		"lines" => return Ok(InputKind::Lines),
+		"chunk" => return Ok(InputKind::Chunk),
+		"numbers" => return Ok(InputKind::Numbers),
+		"records" => return Ok(InputKind::Records),
+		x => return Err(format!("'{}' is not an input kind, expected lines, chunk, numbers or records", x))
+	}
+}
+
+fn next_value<I>(args: &mut I, flag: &str) -> Result<String, String> where I: Iterator<Item = String> {
+	return args.next().ok_or_else(|| format!("{} expects a value", flag));
+}
+
+fn parse_number(value: &str) -> Result<i32, String> {
+	return value.trim().parse::<i32>().map_err(|_| format!("'{}' is not a day number", value));
+}
+
+fn parse_part(value: &str) -> Result<i32, String> {
+	match value.trim() {
+		"1" => return Ok(1),
+		"2" => return Ok(2),
+		x => return Err(format!("'{}' is not a part, expected 1 or 2", x))
+	}
+}
+
+fn parse_iterations(value: &str) -> Result<usize, String> {
+	match value.trim().parse::<usize>() {
+		Ok(count) if count > 0 => return Ok(count),
+		_ => return Err(format!("'{}' is not a positive iteration count", value))
+	}
+}
+
+fn parse_jobs(value: &str) -> Result<usize, String> {
+	match value.trim().parse::<usize>() {
+		Ok(count) if count > 0 => return Ok(count),
+		_ => return Err(format!("'{}' is not a positive job count", value))
+	}
+}
+
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+	match value.trim().parse::<f64>() {
+		Ok(seconds) if seconds > 0.0 && seconds.is_finite() => return Ok(Duration::from_secs_f64(seconds)),
+		_ => return Err(format!("'{}' is not a positive number of seconds", value))
+	}
+}
+
+fn parse_format(value: &str) -> Result<Format, String> {
+	return Format::parse(value).ok_or_else(|| format!("'{}' is not a format, expected text, json or csv", value));
+}
+
+fn parse_report(value: &str) -> Result<(report::Kind, PathBuf), String> {
+	let path = PathBuf::from(value);
+	return match report::Kind::from_path(&path) {
+		Some(kind) => Ok((kind, path)),
+		None => Err(format!("'{}' needs to end in .md or .html to say what kind of report to write", value))
+	};
+}
+
+fn parse_input(value: &str) -> Result<(Option<i32>, Source), String> {
+	match value.split_once('=') {
+		Some((day, path)) if !path.is_empty() && day.trim().parse::<i32>().is_ok() => return Ok((Some(parse_number(day)?), Source::parse(path))),
+		_ => return Ok((None, Source::parse(value)))
+	}
+}
+
+// A bare --input only makes sense when exactly one day is selected, and stdin can only be read once
+fn resolve_inputs(inputs: Vec<(Option<i32>, Source)>, days: &[i32]) -> Result<Vec<(i32, Source)>, String> {
+	let mut resolved: Vec<(i32, Source)> = vec![];
+
+	for (day, source) in inputs {
+		let day = match (day, days) {
+			(Some(day), _) => day,
+			(None, [day]) => *day,
+			(None, _) => return Err(String::from("--input without a day number needs exactly one day selected, use --input N=PATH"))
+		};
+
+		if !days.contains(&day) {
+			return Err(format!("--input was given for day {}, which isn't selected", day));
+		}
+		if resolved.iter().any(|(input_day, _)| *input_day == day) {
+			return Err(format!("day {} was given more than one --input", day));
+		}
+		resolved.push((day, source));
+	}
+
+	if resolved.iter().filter(|(_, source)| *source == Source::Stdin).count() > 1 {
+		return Err(String::from("only one day can read its input from stdin"));
+	}
+
+	return Ok(resolved);
+}
+
+fn parse_day_list(value: &str) -> Result<Vec<i32>, String> {
+	let mut days = vec![];
+
+	for item in value.split(',') {
+		match item.split_once('-') {
+			Some((start, end)) => {
+				let (start, end) = (parse_number(start)?, parse_number(end)?);
+				if start > end { return Err(format!("'{}' is an empty range", item)); }
+				days.extend(start..=end);
+			},
+			None => days.push(parse_number(item)?)
+		}
+	}
+
+	return Ok(days);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(line: &str) -> Result<Options, String> {
+		let available: Vec<i32> = (1..=18).collect();
+		return parse_args(line.split_whitespace().map(String::from), &available, None);
+	}
+
+	#[test]
+	fn defaults_to_everything() {
+		let options = parse("").unwrap();
+		assert_eq!(options.days, (1..=18).collect::<Vec<_>>());
+		assert_eq!(options.parts, vec![1, 2]);
+		assert_eq!(options.format, Format::Text);
+	}
+
+	#[test]
+	fn day_and_part_selection_works() {
+		let options = parse("--day 8 --part 2").unwrap();
+		assert_eq!(options.days, vec![8]);
+		assert_eq!(options.parts, vec![2]);
+	}
+
+	#[test]
+	fn day_lists_work() {
+		assert_eq!(parse("--days 10-14").unwrap().days, vec![10, 11, 12, 13, 14]);
+		assert_eq!(parse("--days 3,1,5-6 --day 3").unwrap().days, vec![1, 3, 5, 6]);
+	}
+
+	#[test]
+	fn format_selection_works() {
+		assert_eq!(parse("--format json").unwrap().format, Format::Json);
+		assert_eq!(parse("--format csv").unwrap().format, Format::Csv);
+		assert!(parse("--format yaml").is_err());
+	}
+
+	#[test]
+	fn job_limits_work() {
+		assert!(parse("").unwrap().jobs >= 1);
+		assert_eq!(parse("--jobs 3").unwrap().jobs, 3);
+		assert_eq!(parse("--jobs 3 --sequential").unwrap().jobs, 1);
+		assert!(parse("--jobs 0").is_err());
+	}
+
+	#[test]
+	fn timeouts_work() {
+		assert_eq!(parse("").unwrap().timeout, None);
+		assert_eq!(parse("--timeout 2.5").unwrap().timeout, Some(Duration::from_millis(2500)));
+		assert!(parse("--timeout 0").is_err());
+		assert!(parse("--timeout soon").is_err());
+	}
+
+	#[test]
+	fn caching_can_be_turned_off() {
+		assert!(parse("").unwrap().cache);
+		assert!(!parse("--no-cache").unwrap().cache);
+	}
+
+	#[test]
+	fn visualize_options_work() {
+		let options = parse("--visualize --jobs 4").unwrap();
+		assert_eq!((options.visualize, options.jobs, options.cache), (Some(Target::Terminal), 1, false));
+
+		let options = parse("--visualize-to frames --jobs 4").unwrap();
+		assert_eq!((options.visualize, options.jobs, options.cache), (Some(Target::Images(PathBuf::from("frames"))), 4, false));
+
+		assert_eq!(parse("").unwrap().visualize, None);
+		assert!(parse("--visualize --bench").is_err());
+		assert!(parse("--visualize-to").is_err());
+	}
+
+	#[test]
+	fn watch_options_work() {
+		assert!(parse("--watch --day 8").unwrap().watch);
+		assert!(!parse("").unwrap().watch);
+		assert!(parse("--watch --bench").is_err());
+		assert!(parse("--watch --day 8 --input -").is_err());
+	}
+
+	#[test]
+	fn bench_options_work() {
+		let options = parse("--bench --iterations 3 --day 15").unwrap();
+		assert!(options.bench);
+		assert_eq!(options.iterations, 3);
+		assert_eq!(parse("--bench").unwrap().iterations, 10);
+		assert!(parse("--iterations 0").is_err());
+	}
+
+	#[test]
+	fn report_options_work() {
+		let options = parse("--report run.md --report run.html").unwrap();
+		assert_eq!(options.reports, vec![(report::Kind::Markdown, PathBuf::from("run.md")), (report::Kind::Html, PathBuf::from("run.html"))]);
+		assert!(parse("").unwrap().reports.is_empty());
+		assert!(parse("--report run.txt").is_err());
+		assert!(parse("--report run.md --bench").is_err());
+	}
+
+	#[test]
+	fn batch_options_work() {
+		assert_eq!(parse("--batch inputs --day 8").unwrap().batch, Some(PathBuf::from("inputs")));
+		assert_eq!(parse("").unwrap().batch, None);
+		assert!(parse("--batch").is_err());
+		assert!(parse("--batch inputs --check").is_err());
+		assert!(parse("--batch inputs --day 8 --input day8.txt").is_err());
+	}
+
+	#[test]
+	fn check_options_work() {
+		let options = parse("--check").unwrap();
+		assert!(options.check);
+		assert_eq!(options.answers, "answers.toml");
+		assert_eq!(parse("--check --answers mine.toml").unwrap().answers, "mine.toml");
+	}
+
+	#[test]
+	fn input_options_work() {
+		let options = parse("--day 4 --input mine.txt").unwrap();
+		assert_eq!(options.source(4), Source::parse("mine.txt"));
+
+		let options = parse("--days 1-2 --input 2=- --data-dir inputs/alice").unwrap();
+		assert_eq!(options.source(1), Source::File(PathBuf::from("inputs/alice/day1.txt")));
+		assert_eq!(options.source(2), Source::Stdin);
+
+		assert_eq!(parse("").unwrap().source(7), Source::parse("data/day7.txt"));
+		assert!(parse("--input mine.txt").is_err());
+		assert!(parse("--day 1 --input 2=mine.txt").is_err());
+		assert!(parse("--days 1-2 --input 1=- --input 2=-").is_err());
+	}
+
+	#[test]
+	fn data_dir_falls_back_to_the_environment() {
+		let available: Vec<i32> = (1..=18).collect();
+		let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+
+		let options = parse_args(args(""), &available, Some(String::from("generated"))).unwrap();
+		assert_eq!(options.source(3), Source::parse("generated/day3.txt"));
+		let options = parse_args(args("--data-dir mine"), &available, Some(String::from("generated"))).unwrap();
+		assert_eq!(options.source(3), Source::parse("mine/day3.txt"));
+	}
+
+	#[test]
+	fn new_day_arguments_work() {
+		let new_day = |line: &str| parse_new_day(line.split_whitespace().map(String::from));
+
+		assert_eq!(new_day("19").unwrap(), NewDay { day: 19, kind: InputKind::Lines, name: String::from("Day 19") });
+		assert_eq!(new_day("--input records 20 --name Jigsaw").unwrap(), NewDay { day: 20, kind: InputKind::Records, name: String::from("Jigsaw") });
+		assert!(new_day("").is_err());
+		assert!(new_day("26").is_err());
+		assert!(new_day("19 --input yaml").is_err());
+		assert!(new_day("19 20").is_err());
+	}
+
+	#[test]
+	fn serve_arguments_work() {
+		let serve = |line: &str| parse_serve(line.split_whitespace().map(String::from));
+
+		let options = serve("").unwrap();
+		assert_eq!((options.address, options.timeout), (String::from("127.0.0.1:2020"), None));
+		assert_eq!(serve("--port 8080 --host 0.0.0.0 --jobs 3 --timeout 2").unwrap(), Serve { address: String::from("0.0.0.0:8080"), jobs: 3, timeout: Some(Duration::from_secs(2)) });
+		assert!(serve("--jobs 0").is_err());
+		assert!(serve("--port 70000").is_err());
+		assert!(serve("8").is_err());
+	}
+
+	#[test]
+	fn generate_arguments_work() {
+		let generate = |line: &str| parse_generate(line.split_whitespace().map(String::from));
+
+		assert_eq!(generate("8").unwrap(), Generate { day: 8, size: 100, seed: None });
+		assert_eq!(generate("--seed 42 16 --size 5").unwrap(), Generate { day: 16, size: 5, seed: Some(42) });
+		assert!(generate("").is_err());
+		assert!(generate("9").is_err());
+		assert!(generate("8 --size lots").is_err());
+	}
+
+	#[test]
+	fn bad_arguments_are_rejected() {
+		assert!(parse("--day 19").is_err());
+		assert!(parse("--days 14-10").is_err());
+		assert!(parse("--part 3").is_err());
+		assert!(parse("--day").is_err());
+		assert!(parse("--frobnicate").is_err());
+	}
+}
\ No newline at end of file
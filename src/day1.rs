@@ -1,26 +1,36 @@
-pub fn part1(data: &[i32]) -> i32 {
-	return search(2020, data[0], &data[1..]).unwrap();
+use crate::checked;
+use crate::error::{Error, Result};
+
+pub const VERSION: &str = "2";
+
+pub fn part1(data: &[i32]) -> Result<i32> {
+	if data.is_empty() { return Err(Error::new("no expense entries")); }
+	let (a, b) = search(2020, data[0], &data[1..]).ok_or_else(|| Error::new("no two entries sum to 2020"))?;
+	return checked::mul(a, b, "the product of the two entries");
 }
 
-pub fn part2(data: &[i32]) -> i32 {
-	return search_three(data[0], &data[1..]).unwrap();
+pub fn part2(data: &[i32]) -> Result<i32> {
+	if data.is_empty() { return Err(Error::new("no expense entries")); }
+	let (a, b, c) = search_three(data[0], &data[1..]).ok_or_else(|| Error::new("no three entries sum to 2020"))?;
+	return checked::product(vec![a, b, c], "the product of the three entries");
 }
 
-fn search_three(base: i32, rest: &[i32]) -> Option<i32> {
+pub fn search_three(base: i32, rest: &[i32]) -> Option<(i32, i32, i32)> {
 	if rest.len() < 2 {
 		return None;
 	}
 
-	match search(2020 - base, rest[0], &rest[1..]) {
-		Some(value) => return Some(value * base),
+	// An entry so negative that 2020 minus it overflows can't be part of the answer
+	match 2020i32.checked_sub(base).and_then(|target| search(target, rest[0], &rest[1..])) {
+		Some((a, b)) => return Some((base, a, b)),
 		None => return search_three(rest[0], &rest[1..])
 	}
 }
 
-fn search(target: i32, base: i32, rest: &[i32]) -> Option<i32> {
+pub fn search(target: i32, base: i32, rest: &[i32]) -> Option<(i32, i32)> {
   for other in rest.iter() {
-    if base + other == target {
-      return Some(base * other);
+    if base.checked_add(*other) == Some(target) {
+      return Some((base, *other));
     }
   }
 
@@ -1,65 +1,66 @@
-pub fn part1(data: &Vec<String>) -> i32 {
+use crate::biguint::BigUint;
+use crate::checked;
+use crate::error::{self, Error, Result};
+
+pub const VERSION: &str = "2";
+
+pub fn part1(data: &[String]) -> Result<i32> {
 	return process_adapters(data);
 }
 
-pub fn part2(data: &Vec<String>) -> i64 {
-	let values = process_data(data);
-	let children = find_children(&values);
-	let mut counts: Vec<i64> = vec![];
-	counts.resize(values.len(), 0);
-	*counts.last_mut().unwrap() = 1;
-
-	for i in (0..values.len()).rev() {
-		counts[i] = {
-			let mut total = 0;
-			for child in children[i].iter() {
-				total += counts[*child as usize];
-			}
-			total
-		};
+// The number of arrangements roughly multiplies by 1.8 for every adapter in a long run of consecutive ones, so a
+// couple of hundred of them is already past u128
+pub fn part2(data: &[String]) -> Result<BigUint> {
+	let values = process_data(data)?;
+	let children = find_children(&values)?;
+	let mut counts: Vec<BigUint> = vec![BigUint::zero(); values.len()];
+	*counts.last_mut().unwrap() = BigUint::from(1u64);
+
+	for i in (0..values.len() - 1).rev() {
+		counts[i] = children[i].iter().filter(|child| **child as usize != i).map(|child| &counts[*child as usize]).sum();
 	}
 
-	return *counts.first().unwrap();
+	return Ok(counts.swap_remove(0));
 }
 
-fn find_children(data: &Vec<i32>) -> Vec<Vec<i32>> {
+pub fn find_children(data: &[i32]) -> Result<Vec<Vec<i32>>> {
 	let mut children_list = vec![];
 	for i in 0..data.len() {
 		let mut single_list = vec![];
 
 		for j in i..data.len() {
-			if data[j] - data[i] <= 3 { single_list.push(j as i32); }
+			if checked::sub(data[j], data[i], "the gap between two adapters")? <= 3 { single_list.push(j as i32); }
 		}
 		children_list.push(single_list);
 	}
 
-	return children_list;
+	return Ok(children_list);
 }
 
-fn process_data(data: &Vec<String>) -> Vec<i32> {
-	let mut values: Vec<i32> = data.iter().map(|line| line.parse::<i32>().unwrap()).collect();
+pub fn process_data(data: &[String]) -> Result<Vec<i32>> {
+	let mut values: Vec<i32> = error::parse_lines(data, error::parse_number::<i32>)?;
 	values.push(0);
 	values.sort();
-	values.push(values.last().unwrap() + 3);
+	values.push(checked::add(values[values.len() - 1], 3, "the device's joltage")?);
 
-	return values;
+	return Ok(values);
 }
 
-fn process_adapters(data: &Vec<String>) -> i32 {
-	let values = process_data(data);
+pub fn process_adapters(data: &[String]) -> Result<i32> {
+	let values = process_data(data)?;
 
 	let mut ones = 0;
 	let mut threes = 0;
 
 	for i in 0..(values.len() - 1) {
-		match values[i+1] - values[i] {
+		match checked::sub(values[i+1], values[i], "the gap between two adapters")? {
 			1 => ones += 1,
 			3 => threes += 1,
-			_ => panic!("oh this isn't working at all!")
+			_ => return Err(Error::new(format!("can't chain the {} jolt adapter into the {} jolt adapter", values[i], values[i+1])))
 		}
 	}
 
-	return ones * threes;
+	return checked::mul(ones, threes, "the product of the differences");
 }
 
 #[cfg(test)]
@@ -98,9 +99,9 @@ mod tests {
 2
 34
 10
-3".lines().map(|line| String::from(line)).collect();
+3".lines().map(String::from).collect();
 
-		assert_eq!(process_adapters(&data), 220);
+		assert_eq!(process_adapters(&data).unwrap(), 220);
 	}
 
 	#[test]
@@ -135,8 +136,23 @@ mod tests {
 2
 34
 10
-3".lines().map(|line| String::from(line)).collect();
+3".lines().map(String::from).collect();
+
+		assert_eq!(part2(&data).unwrap().to_string(), "19208");
+	}
+
+	#[test]
+	fn arrangements_can_outgrow_u128() {
+		let data: Vec<String> = (1..=200).map(|jolts| jolts.to_string()).collect();
+		assert_eq!(part2(&data).unwrap().to_string(), "52622583840983769603765180599790256716084480555530641");
+	}
+
+	#[test]
+	fn joltage_overflow_is_reported() {
+		let data = vec![i32::MAX.to_string()];
+		assert_eq!(part1(&data).unwrap_err(), Error::new("the device's joltage overflows i32"));
 
-		assert_eq!(part2(&data), 19208);
+		let data = vec![String::from("-2147483648"), String::from("2147483000")];
+		assert_eq!(part2(&data).unwrap_err(), Error::new("the gap between two adapters overflows i32"));
 	}
 }
\ No newline at end of file
@@ -1,33 +1,32 @@
 use std::fmt;
 
-pub fn part1(data: &Vec<String>) -> usize {
-	let mut seats = Seating::new(data, enumerate_neighbors);
-	while seats.step(4) {}
-	return seats.count();
+use crate::cancel;
+use crate::error::Result;
+use crate::grid::{Grid, ADJACENT};
+use crate::visualize::{self, Frame};
+
+pub const VERSION: &str = "1";
+
+pub fn part1(data: &[String]) -> Result<usize> {
+	let mut seats = Seating::new(data, enumerate_neighbors)?;
+	while seats.step(4)? {}
+	return Ok(seats.count());
 }
 
-pub fn part2(data: &Vec<String>) -> usize {
-	let mut seats = Seating::new(data, enumerate_sight_neighbors);
-	while seats.step(5) {}
-	return seats.count();
+pub fn part2(data: &[String]) -> Result<usize> {
+	let mut seats = Seating::new(data, enumerate_sight_neighbors)?;
+	while seats.step(5)? {}
+	return Ok(seats.count());
 }
 
-struct Seating {
-	seats: Vec<Space>,
+pub struct Seating {
+	seats: Grid<Space>,
+	rounds: usize
 }
 
 struct Space {
 	value: char,
-	neighbors: Vec<usize>
-}
-
-impl Space {
-	fn new(value: char, neighbors: Vec<usize>) -> Space {
-		match value {
-			'.' => Space { value: value, neighbors: vec![] },
-			x => Space { value: x, neighbors: neighbors }
-		}
-	}
+	neighbors: Vec<(usize, usize)>
 }
 
 impl PartialEq<Space> for Space {
@@ -49,48 +48,47 @@ impl fmt::Debug for Space {
 }
 
 impl Seating {
-	fn new<F>(data: &Vec<String>, neighbor_finder: F) -> Seating where F: Fn(usize, usize, usize, usize, &Vec<char>) -> Vec<usize> {
-		let height = data.len();
-		let width = data[0].len();
-		let chars: Vec<char> = data.iter().map(|line| line.chars()).flatten().collect();
-
-		let mut neighbor_vec = vec![];
-		for y in 0..height {
-			for x in 0..width {
-				neighbor_vec.push(neighbor_finder(x, y, width, height, &chars));
-			}
+	pub fn new<F>(data: &[String], neighbor_finder: F) -> Result<Seating> where F: Fn(&Grid<char>, usize, usize) -> Vec<(usize, usize)> {
+		let layout = Grid::parse(data, |c| match c {
+			'.' | 'L' | '#' => Some(c),
+			_ => None
+		})?;
+
+		// Floor never changes, so it doesn't need to know its neighbours
+		let mut seats = layout.map(|c| Space { value: *c, neighbors: vec![] });
+		for (x, y) in layout.positions() {
+			if layout[(x, y)] != '.' { seats[(x, y)].neighbors = neighbor_finder(&layout, x, y); }
 		}
 
-		let spaces: Vec<Space> = chars.iter().zip(neighbor_vec).map(|(c, n)| Space::new(*c, n)).collect();
-		return Seating { seats: spaces };
+		return Ok(Seating { seats, rounds: 0 });
 	}
 
-	fn step(&mut self, neighbor_limit: usize) -> bool {
-		let mut new_seats: Vec<char> = vec![];
-
-		for seat in &self.seats {
-			let new_value = self.process_seat(&seat, neighbor_limit);
-			new_seats.push(new_value);
-		}
+	pub fn step(&mut self, neighbor_limit: usize) -> Result<bool> {
+		cancel::check()?;
+		let new_seats: Grid<char> = self.seats.map(|seat| self.process_seat(seat, neighbor_limit));
 
-		if self.seats == new_seats { return false; }
+		if self.seats.iter().zip(new_seats.iter()).all(|(seat, value)| seat == value) { return Ok(false); }
 
-		// Well this sucks
-		for i in 0..new_seats.len() {
-			self.seats[i].value = new_seats[i];
+		for (x, y) in new_seats.positions() {
+			self.seats[(x, y)].value = new_seats[(x, y)];
 		}
 
-		return true;
+		self.rounds += 1;
+		visualize::show(|| Frame::new(format!("round {}: {} occupied", self.rounds, self.count()), new_seats));
+		return Ok(true);
 	}
 
-	fn count(&self) -> usize {
+	pub fn count(&self) -> usize {
 		return self.seats.iter().filter(|c| **c == '#').count();
 	}
 
+	pub fn render(&self) -> String {
+		return self.seats.render(|seat| seat.value);
+	}
+
 	fn process_seat(&self, seat: &Space, limit: usize) -> char {
-		let neighbor_count = seat.neighbors.iter().map(|index| &self.seats[*index]).filter(|c| c.value == '#').count();
-		let new_value = match_seat(seat.value, neighbor_count, limit);
-		return new_value;
+		let neighbor_count = seat.neighbors.iter().filter(|position| self.seats[**position] == '#').count();
+		return match_seat(seat.value, neighbor_count, limit);
 	}
 }
 
@@ -105,59 +103,13 @@ fn match_seat(seat: char, neighbors: usize, limit: usize) -> char {
 	}
 }
 
-fn coords(x: usize, y: usize, coord_factor: usize) -> usize {
-	return y * coord_factor + x;
+pub fn enumerate_neighbors(layout: &Grid<char>, x: usize, y: usize) -> Vec<(usize, usize)> {
+	return layout.neighbors(x, y, &ADJACENT).collect();
 }
 
-fn enumerate_neighbors(x: usize, y: usize, maxwidth: usize, maxheight: usize, _: &Vec<char>) -> Vec<usize> {
-	let x_copy = x as i32;
-	let y_copy = y as i32;
-
-	let mut neighbor_coords = vec![];
-	for i in (x_copy - 1)..=(x_copy + 1) {
-		for j in (y_copy - 1)..=(y_copy + 1) {
-			if pair_okay(i, j, x, y, maxwidth, maxheight) {
-				neighbor_coords.push(coords(i as usize, j as usize, maxwidth));
-			}
-		}
-	}
-	return neighbor_coords;
-}
-
-fn enumerate_sight_neighbors(x: usize, y: usize, maxwidth: usize, maxheight: usize, floor: &Vec<char>) -> Vec<usize> {
-	let mut neighbors = vec![];
-
-	for x_move in &[-1, 0, 1] {
-		for y_move in &[-1, 0, 1] {
-			let mut x_here = x as i32;
-			let mut y_here = y as i32;
-
-			if *x_move == 0 && *y_move == 0 { continue; }
-
-			loop {
-				x_here += x_move;
-				y_here += y_move;
-
-				if !pair_okay(x_here, y_here, x, y, maxwidth, maxheight) { break; }
-				let here = coords(x_here as usize, y_here as usize, maxwidth);
-
-				if floor[here] != '.' { 
-					neighbors.push(here);
-					break;
-				}
-			}
-		}
-	}
-	return neighbors;
-}
-
-fn pair_okay(x: i32, y: i32, original_x: usize, original_y: usize, max_x: usize, max_y: usize) -> bool {
-	let x_okay = x >= 0 && (x as usize) < max_x;
-	let y_okay = y >= 0 && (y as usize) < max_y;
-
-	let not_origin = (x as usize) != original_x || (y as usize) != original_y;
-
-	return x_okay && y_okay && not_origin;
+// The first seat in each direction counts, however much floor is in between
+pub fn enumerate_sight_neighbors(layout: &Grid<char>, x: usize, y: usize) -> Vec<(usize, usize)> {
+	return layout.line_of_sight(x, y, &ADJACENT, |c| *c != '.');
 }
 
 #[cfg(test)]
@@ -184,10 +136,10 @@ mod tests {
 ..#.#.....
 ##########
 #.######.#
-#.#####.##".lines().map(|line| String::from(line)).collect();
+#.#####.##".lines().map(String::from).collect::<Vec<_>>();
 
-		let mut seats = Seating::new(&data, enumerate_neighbors);
-		seats.step(4);
+		let mut seats = Seating::new(&data, enumerate_neighbors).unwrap();
+		seats.step(4).unwrap();
 
 		let stepped_data = "#.LL.L#.##
 #LLLLLL.L#
@@ -198,9 +150,9 @@ L.L.L..L..
 ..L.L.....
 #LLLLLLLL#
 #.LLLLLL.L
-#.#LLLL.##".lines().map(|line| String::from(line)).collect();
+#.#LLLL.##".lines().map(String::from).collect::<Vec<_>>();
 
-		let stepped_seats = Seating::new(&stepped_data, enumerate_neighbors);
+		let stepped_seats = Seating::new(&stepped_data, enumerate_neighbors).unwrap();
 
 		assert_eq!(seats.count(), stepped_seats.count());
 		assert_eq!(seats.seats, stepped_seats.seats);
@@ -217,10 +169,10 @@ L.L.L..L..
 ..#.#.....
 ##########
 #.######.#
-#.#####.##".lines().map(|line| String::from(line)).collect();
+#.#####.##".lines().map(String::from).collect::<Vec<_>>();
 
-		let mut seats = Seating::new(&data, enumerate_sight_neighbors);
-		seats.step(5);
+		let mut seats = Seating::new(&data, enumerate_sight_neighbors).unwrap();
+		seats.step(5).unwrap();
 
 		let stepped_data = "#.LL.LL.L#
 #LLLLLL.LL
@@ -231,12 +183,20 @@ L.LLLLL.LL
 ..L.L.....
 LLLLLLLLL#
 #.LLLLLL.L
-#.LLLLL.L#".lines().map(|line| String::from(line)).collect();
+#.LLLLL.L#".lines().map(String::from).collect::<Vec<_>>();
 
-		let stepped_seats = Seating::new(&stepped_data, enumerate_sight_neighbors);
+		let stepped_seats = Seating::new(&stepped_data, enumerate_sight_neighbors).unwrap();
 
 		assert_eq!(seats.count(), stepped_seats.count());
 		assert_eq!(seats.seats, stepped_seats.seats);
+	}
+
+	#[test]
+	fn render_draws_the_layout() {
+		let data = "#.##
+L.L#".lines().map(String::from).collect::<Vec<_>>();
 
+		let seats = Seating::new(&data, enumerate_sight_neighbors).unwrap();
+		assert_eq!(seats.render(), "#.##\nL.L#\n");
 	}
 }
\ No newline at end of file
@@ -1,90 +1,157 @@
-pub fn part1(data: &Vec<String>) -> i32 {
+use crate::checked;
+use crate::error::{self, Error, Result};
+use crate::grid::Grid;
+use crate::visualize::{self, Frame};
+
+pub const VERSION: &str = "2";
+
+pub fn part1(data: &[String]) -> Result<i32> {
 	let mut ship = Ship::new();
 
-	for line in data {
-		let (action, value) = parse_instruction(line);
-		ship.move_ship(action, value);
+	for (index, (action, value)) in error::parse_lines(data, parse_instruction)?.into_iter().enumerate() {
+		ship.move_ship(action, value)?;
+		visualize::show(|| Frame::new(format!("step {}: {}{}", index + 1, action, value), ship.trajectory()));
 	}
 
-	return ship.distance();
+	let distance = ship.distance()?;
+	visualize::show(|| Frame::new(format!("distance {}", distance), ship.trajectory()));
+	return Ok(distance);
 }
 
-pub fn part2(data: &Vec<String>)-> i32 {
+pub fn part2(data: &[String])-> Result<i32> {
 	let mut ship = Ship::new();
 
-	for line in data {
-		let (action, value) = parse_instruction(line);
-		ship.move_waypoint(action, value);
+	for (index, (action, value)) in error::parse_lines(data, parse_instruction)?.into_iter().enumerate() {
+		ship.move_waypoint(action, value)?;
+		visualize::show(|| Frame::new(format!("step {}: {}{}", index + 1, action, value), ship.trajectory()));
 	}
 
-	return ship.distance();
+	let distance = ship.distance()?;
+	visualize::show(|| Frame::new(format!("distance {}", distance), ship.trajectory()));
+	return Ok(distance);
 }
 
-fn parse_instruction(instruction: &str) -> (char, i32) {
-	let action = instruction.chars().next().unwrap();
-	let value = &instruction[1..].parse::<i32>().unwrap();
+pub fn parse_instruction(instruction: &str) -> Result<(char, i32)> {
+	let action = instruction.chars().next().ok_or_else(|| Error::new("empty instruction"))?;
+	if !"NSEWLRF".contains(action) { return Err(Error::new(format!("unknown action '{}'", action))); }
+
+	let value = error::parse_number::<i32>(&instruction[action.len_utf8()..])?;
+	if (action == 'L' || action == 'R') && value % 90 != 0 { return Err(Error::new(format!("can only turn by multiples of 90 degrees, not {}", value))); }
 
-	return (action, *value);
+	return Ok((action, value));
 }
 
-struct Ship {
+// The most cells a trajectory frame takes up, across and down
+const TRACK_SIZE: (i64, i64) = (120, 60);
+
+pub struct Ship {
 	facing: char,
 	position: (i32, i32),
-	waypoint: (i32, i32)
+	waypoint: (i32, i32),
+	track: Vec<(i32, i32)>
+}
+
+impl Default for Ship {
+	fn default() -> Ship {
+		return Ship::new();
+	}
 }
 
 impl Ship {
-	fn new() -> Ship {
-		return Ship { facing: 'E', position: (0,0) , waypoint: (10,1) };
+	pub fn new() -> Ship {
+		return Ship { facing: 'E', position: (0,0) , waypoint: (10,1), track: vec![(0, 0)] };
 	}
 	
-	fn distance(&self) -> i32 {
-		return self.position.0.abs() + self.position.1.abs();
+	pub fn distance(&self) -> Result<i32> {
+		let x = self.position.0.checked_abs().ok_or_else(|| checked::overflow::<i32>("the distance"))?;
+		let y = self.position.1.checked_abs().ok_or_else(|| checked::overflow::<i32>("the distance"))?;
+		return checked::add(x, y, "the distance");
 	}
 
-	fn move_ship(&mut self, direction: char, amount: i32) {
+	pub fn move_ship(&mut self, direction: char, amount: i32) -> Result<()> {
 		let dir = if direction != 'F' { direction } else { self.facing };
 		match dir {
-			'N' => self.position.1 += amount,
-			'S' => self.position.1 -= amount,
-			'E' => self.position.0 += amount,
-			'W' => self.position.0 -= amount,
+			'N' => self.position.1 = checked::add(self.position.1, amount, "the ship's position")?,
+			'S' => self.position.1 = checked::sub(self.position.1, amount, "the ship's position")?,
+			'E' => self.position.0 = checked::add(self.position.0, amount, "the ship's position")?,
+			'W' => self.position.0 = checked::sub(self.position.0, amount, "the ship's position")?,
 			'R' => for _ in 0..(amount / 90) { self.facing = Ship::turn_right(self.facing) },
 			'L' => for _ in 0..(amount / 90) { self.facing = Ship::turn_left(self.facing) }
 			_ => panic!("bad action sent")
 		}
+		self.record();
+		return Ok(());
 	}
 
-	fn move_waypoint(&mut self, direction: char, amount: i32) {
+	pub fn move_waypoint(&mut self, direction: char, amount: i32) -> Result<()> {
 		match direction {
-			'N' => self.waypoint.1 += amount,
-			'S' => self.waypoint.1 -= amount,
-			'E' => self.waypoint.0 += amount,
-			'W' => self.waypoint.0 -= amount,
-			'L' => for _ in 0..(amount / 90) { self.rotate_waypoint_left() },
-			'R' => for _ in 0..(amount / 90) { self.rotate_waypoint_right() },
-			'F' => self.move_to_waypoint(amount),
+			'N' => self.waypoint.1 = checked::add(self.waypoint.1, amount, "the waypoint")?,
+			'S' => self.waypoint.1 = checked::sub(self.waypoint.1, amount, "the waypoint")?,
+			'E' => self.waypoint.0 = checked::add(self.waypoint.0, amount, "the waypoint")?,
+			'W' => self.waypoint.0 = checked::sub(self.waypoint.0, amount, "the waypoint")?,
+			'L' => for _ in 0..(amount / 90) { self.rotate_waypoint_left()? },
+			'R' => for _ in 0..(amount / 90) { self.rotate_waypoint_right()? },
+			'F' => self.move_to_waypoint(amount)?,
 			_ => panic!("unexpected action")
 		}
+		return Ok(());
 	}
 
-	fn move_to_waypoint(&mut self, amount: i32) {
-		self.position.0 += self.waypoint.0 * amount;
-		self.position.1 += self.waypoint.1 * amount;
+	pub fn move_to_waypoint(&mut self, amount: i32) -> Result<()> {
+		let x = checked::mul(self.waypoint.0, amount, "the ship's position")?;
+		let y = checked::mul(self.waypoint.1, amount, "the ship's position")?;
+		self.position = (checked::add(self.position.0, x, "the ship's position")?, checked::add(self.position.1, y, "the ship's position")?);
+		self.record();
+		return Ok(());
 	}
 
-	fn rotate_waypoint_left(&mut self) {
+	// The track is only kept when someone is going to look at it
+	fn record(&mut self) {
+		if visualize::enabled() && self.track.last() != Some(&self.position) {
+			self.track.push(self.position);
+		}
+	}
+
+	// Everywhere the ship has been, shrunk to fit TRACK_SIZE with north at the top; S is the start, X is where it is now.
+	// Worked out in i64, since the track can span the whole i32 range
+	pub fn trajectory(&self) -> Grid<char> {
+		let (min_x, max_x) = (self.track.iter().map(|p| p.0 as i64).min().unwrap_or(0), self.track.iter().map(|p| p.0 as i64).max().unwrap_or(0));
+		let (min_y, max_y) = (self.track.iter().map(|p| p.1 as i64).min().unwrap_or(0), self.track.iter().map(|p| p.1 as i64).max().unwrap_or(0));
+		let scale = ((max_x - min_x) / (TRACK_SIZE.0 - 1)).max((max_y - min_y) / (TRACK_SIZE.1 - 1)) + 1;
+
+		let cell = |(x, y): (i32, i32)| ((x as i64 - min_x) / scale, (max_y - y as i64) / scale);
+		let mut cells = Grid::new(((max_x - min_x) / scale + 1) as usize, ((max_y - min_y) / scale + 1) as usize, '.');
+
+		for leg in self.track.windows(2) {
+			let (from, to) = (cell(leg[0]), cell(leg[1]));
+			let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).max(1);
+			for step in 0..=steps {
+				let x = from.0 + (to.0 - from.0) * step / steps;
+				let y = from.1 + (to.1 - from.1) * step / steps;
+				cells[(x as usize, y as usize)] = '*';
+			}
+		}
+
+		let (start, end) = (cell(self.track[0]), cell(*self.track.last().unwrap_or(&self.position)));
+		cells[(start.0 as usize, start.1 as usize)] = 'S';
+		cells[(end.0 as usize, end.1 as usize)] = 'X';
+		return cells;
+	}
+
+	pub fn rotate_waypoint_left(&mut self) -> Result<()> {
 		let x = self.waypoint.0;
 		let y = self.waypoint.1;
-		self.waypoint.0 = -y;
+		self.waypoint.0 = checked::sub(0, y, "the waypoint")?;
 		self.waypoint.1 = x;
+		return Ok(());
 	}
 
-	fn rotate_waypoint_right(&mut self) {
+	pub fn rotate_waypoint_right(&mut self) -> Result<()> {
 		let x = self.waypoint.0;
 		let y = self.waypoint.1;
 		self.waypoint.0 = y;
-		self.waypoint.1 = -x;
+		self.waypoint.1 = checked::sub(0, x, "the waypoint")?;
+		return Ok(());
 	}
 
 	fn turn_left(facing: char) -> char {
@@ -111,6 +178,8 @@ impl Ship {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use std::sync::{Arc, Mutex};
+	use crate::visualize::Sink;
 
 	#[test]
 	fn ship_works() {
@@ -118,22 +187,61 @@ mod tests {
 N3
 F7
 R90
-F11".lines().map(|line| String::from(line)).collect();
+F11".lines().map(String::from).collect();
 
 		let mut ship = Ship::new();
 		for line in &data {
-			let (action, amount) = parse_instruction(line);
-			ship.move_ship(action, amount);
+			let (action, amount) = parse_instruction(line).unwrap();
+			ship.move_ship(action, amount).unwrap();
 		}
 
-		let distance = ship.distance();
+		let distance = ship.distance().unwrap();
 		assert_eq!(distance.abs(), 25);
 	}
 
+	struct Keep(Arc<Mutex<Vec<Frame>>>);
+
+	impl Sink for Keep {
+		fn frame(&mut self, frame: &Frame) -> std::io::Result<()> {
+			self.0.lock().unwrap().push(frame.clone());
+			return Ok(());
+		}
+	}
+
+	#[test]
+	fn trajectory_is_drawn_when_visualizing() {
+		let data: Vec<String> = "F10\nN3\nF7\nR90\nF11".lines().map(String::from).collect();
+		let frames = Arc::new(Mutex::new(vec![]));
+
+		assert_eq!(visualize::with_sink(Box::new(Keep(frames.clone())), || part1(&data)).unwrap(), 25);
+
+		let frames = frames.lock().unwrap();
+		assert_eq!(frames.iter().map(|frame| frame.title.as_str()).collect::<Vec<_>>(), vec!["step 1: F10", "step 2: N3", "step 3: F7", "step 4: R90", "step 5: F11", "distance 25"]);
+		assert_eq!(frames[0].cells.render(|c| *c), "S*********X\n");
+
+		let last = frames.last().unwrap();
+		assert_eq!(last.cells.render(|c| *c).lines().collect::<Vec<_>>()[0], "..........********");
+		assert_eq!(last.cells.height(), 12);
+		assert_eq!(last.cells[(0, 3)], 'S');
+		assert_eq!(last.cells[(17, 11)], 'X');
+	}
+
+	#[test]
+	fn trajectories_can_span_every_i32() {
+		let data: Vec<String> = vec![format!("E{}", i32::MAX), format!("W{}", i32::MAX), format!("W{}", i32::MAX)];
+		let frames = Arc::new(Mutex::new(vec![]));
+
+		assert_eq!(visualize::with_sink(Box::new(Keep(frames.clone())), || part1(&data)).unwrap(), i32::MAX);
+
+		let last = frames.lock().unwrap().last().unwrap().cells.render(|c| *c);
+		assert!(last.lines().all(|line| line.len() <= TRACK_SIZE.0 as usize));
+		assert_eq!(last.chars().filter(|c| *c == 'S').count(), 1);
+	}
+
 	#[test]
 	fn move_to_waypoint_works() {
 		let mut ship = Ship::new();
-		ship.move_waypoint('F', 10);
+		ship.move_waypoint('F', 10).unwrap();
 
 		assert_eq!(ship.position.0, 100);
 		assert_eq!(ship.position.1, 10);
@@ -142,12 +250,12 @@ F11".lines().map(|line| String::from(line)).collect();
 	#[test]
 	fn waypoint_rotation_works() {
 		let mut ship = Ship::new();
-		let waypoint = ship.waypoint.clone();
+		let waypoint = ship.waypoint;
 
-		ship.rotate_waypoint_right();
-		ship.rotate_waypoint_right();
-		ship.rotate_waypoint_right();
-		ship.rotate_waypoint_right();
+		ship.rotate_waypoint_right().unwrap();
+		ship.rotate_waypoint_right().unwrap();
+		ship.rotate_waypoint_right().unwrap();
+		ship.rotate_waypoint_right().unwrap();
 
 		assert_eq!(waypoint, ship.waypoint);
 	}
@@ -156,19 +264,19 @@ F11".lines().map(|line| String::from(line)).collect();
 	fn what_the_heck() {
 		let mut ship = Ship::new();
 
-		ship.move_waypoint('F', 10);
+		ship.move_waypoint('F', 10).unwrap();
 		assert_eq!(ship.position.0, 100); assert_eq!(ship.position.1, 10);
 
-		ship.move_waypoint('N', 3);
+		ship.move_waypoint('N', 3).unwrap();
 		assert_eq!(ship.waypoint.0, 10); assert_eq!(ship.waypoint.1, 4);
 
-		ship.move_waypoint('F', 7);
+		ship.move_waypoint('F', 7).unwrap();
 		assert_eq!(ship.position.0, 170); assert_eq!(ship.position.1, 38);
 
-		ship.move_waypoint('R', 90);
+		ship.move_waypoint('R', 90).unwrap();
 		assert_eq!(ship.waypoint.0, 4); assert_eq!(ship.waypoint.1, -10);
 
-		ship.move_waypoint('F', 11);
+		ship.move_waypoint('F', 11).unwrap();
 		assert_eq!(ship.position.0, 214); assert_eq!(ship.position.1, -72);
 	}
 
@@ -178,15 +286,24 @@ F11".lines().map(|line| String::from(line)).collect();
 N3
 F7
 R90
-F11".lines().map(|line| String::from(line)).collect();
+F11".lines().map(String::from).collect();
 
 		let mut ship = Ship::new();
 		for line in &data {
-			let (action, amount) = parse_instruction(line);
-			ship.move_waypoint(action, amount);
+			let (action, amount) = parse_instruction(line).unwrap();
+			ship.move_waypoint(action, amount).unwrap();
 		}
 
-		let distance = ship.distance();
+		let distance = ship.distance().unwrap();
 		assert_eq!(distance.abs(), 286);
 	}
+
+	#[test]
+	fn position_overflow_is_reported() {
+		let data: Vec<String> = vec![format!("E{}", i32::MAX - 10), String::from("F20")];
+		assert_eq!(part1(&data).unwrap_err(), Error::new("the ship's position overflows i32"));
+
+		let data: Vec<String> = vec![format!("N{}", i32::MAX - 1), String::from("F2")];
+		assert_eq!(part2(&data).unwrap_err(), Error::new("the ship's position overflows i32"));
+	}
 }
\ No newline at end of file
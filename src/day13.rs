@@ -1,20 +1,31 @@
-pub fn part1(data: &Vec<String>) -> usize {
-	let departure_time = data[0].parse::<usize>().unwrap();
-	let buses = parse_buses(&data[1]);
+use crate::checked;
+use crate::error::{self, Error, Result};
 
-	let available_times = buses.iter().map(|bus| (calculate_bus_time(*bus, departure_time), *bus));
-	let (actual_time, actual_bus) = available_times.min_by(|(time, _), (timeb, _)| time.cmp(timeb)).unwrap();
+pub const VERSION: &str = "2";
 
-	return (actual_time - departure_time) * actual_bus;
+pub fn part1(data: &[String]) -> Result<usize> {
+	let departure_time = error::parse_number::<usize>(schedule_line(data, 0)?).map_err(|e| e.with_line(1))?;
+	let buses = parse_buses(schedule_line(data, 1)?).map_err(|e| e.with_line(2))?;
+
+	let available_times = buses.iter().map(|bus| calculate_bus_time(*bus, departure_time).map(|time| (time, *bus))).collect::<Result<Vec<_>>>()?;
+	let (actual_time, actual_bus) = available_times.into_iter().min_by(|(time, _), (timeb, _)| time.cmp(timeb)).ok_or_else(|| Error::at_line(2, "no buses are running"))?;
+
+	return checked::mul(actual_time - departure_time, actual_bus, "the wait times the bus id");
 }
 
 // Nicked the part2 solution off of someone on reddit because after a few hours I wasn't having any luck or any fun
-pub fn part2(data: &Vec<String>) -> i128 {
-	let buses: Vec<Bus> = parse_relative_buses(&data[1]).iter().enumerate().filter(|(_, b)| b.is_some()).map(|(index, b)| Bus { id: b.unwrap(), offset: index as i128 }).collect();
+pub fn part2(data: &[String]) -> Result<i128> {
+	let relative_buses = parse_relative_buses(schedule_line(data, 1)?).map_err(|e| e.with_line(2))?;
+	let buses: Vec<Bus> = relative_buses.iter().enumerate().filter_map(|(index, b)| b.map(|id| Bus { id, offset: index as i128 })).collect();
 	let offsets: Vec<i128> = buses.iter().map(|b| b.offset).collect();
 	let values: Vec<i128> = buses.iter().map(|b| b.id).collect();
 	
-	values.iter().product::<i128>() - remainder(&offsets, &values).unwrap()
+	let remainder = remainder(&offsets, &values).map_err(|e| e.with_line(2))?;
+	checked::sub(checked::product(values.iter().copied(), "the product of the bus ids")?, remainder, "the earliest timestamp")
+}
+
+fn schedule_line(data: &[String], index: usize) -> Result<&str> {
+	return data.get(index).map(|line| line.as_str()).ok_or_else(|| Error::new("expected a departure time line and a bus list line"));
 }
 
 fn egcd(a: i128, b: i128) -> (i128, i128, i128) {
@@ -35,16 +46,20 @@ fn mod_inv(x: i128, n: i128) -> Option<i128> {
 	else { None }
 }
 
-fn remainder(offsets: &[i128], buses: &[i128]) -> Option<i128> {
-	let total = buses.iter().product::<i128>();
+pub fn remainder(offsets: &[i128], buses: &[i128]) -> Result<i128> {
+	let what = "the product of the bus ids";
+	let total = checked::product(buses.iter().copied(), what)?;
 
 	let mut sum = 0;
 	for (&offset, &bus) in offsets.iter().zip(buses) {
 		let x = total / bus;
-		sum += offset * mod_inv(x, bus)? * x;
+		let inverse = mod_inv(x, bus).ok_or_else(|| Error::new("the bus ids need to be pairwise coprime"))?;
+		// Reducing each term keeps the running sum below the product of the ids
+		let term = checked::mul(checked::mul(offset % bus, inverse, what)?, x, what)? % total;
+		sum = checked::add(sum, term, what)? % total;
 	}
 
-	Some(sum % total)
+	Ok(sum % total)
 }
 
 #[derive(Copy, Clone)]
@@ -53,24 +68,32 @@ struct Bus {
 	offset: i128
 }
 
-fn calculate_bus_time(bus: usize, time: usize) -> usize {
-	return (time / bus) * bus + bus;
+fn calculate_bus_time(bus: usize, time: usize) -> Result<usize> {
+	return checked::add((time / bus) * bus, bus, "the next departure");
 }
 
-fn parse_relative_buses(data: &str) -> Vec<Option<i128>> {
+pub fn parse_relative_buses(data: &str) -> Result<Vec<Option<i128>>> {
 	let mut buses = vec![];
 	for c in data.split(',') {
 		match c {
 			"x" => buses.push(None),
-			x => buses.push(Some(x.parse::<i128>().unwrap()))
+			x => buses.push(Some(parse_bus(x)?))
 		}
 	}
 
-	return buses;
+	return Ok(buses);
+}
+
+pub fn parse_buses(data: &str) -> Result<Vec<usize>> {
+	return data.split(',').filter(|s| *s != "x").map(|n| parse_bus(n).map(|bus| bus as usize)).collect();	
 }
 
-fn parse_buses(data: &str) -> Vec<usize> {
-	return data.split(',').filter(|s| *s != "x").map(|n| n.parse::<usize>().unwrap()).collect();	
+// A bus id is also its loop time, so zero would never arrive and breaks both parts' maths
+fn parse_bus(data: &str) -> Result<i128> {
+	match error::parse_number::<i128>(data)? {
+		x if x > 0 => return Ok(x),
+		x => return Err(Error::new(format!("bus id {} has to be positive", x)))
+	}
 }
 
 #[cfg(test)]
@@ -80,8 +103,16 @@ mod tests {
 	#[test]
 	fn day13_part2_works() {
 		let data = "1000
-17,x,13,19".lines().map(|line| String::from(line)).collect();
+17,x,13,19".lines().map(String::from).collect::<Vec<_>>();
+
+		assert_eq!(part2(&data).unwrap(), 3417);
+	}
+
+	#[test]
+	fn overflow_is_reported() {
+		let data = "1000
+1000000000000000003,1000000000000000009,1000000000000000031".lines().map(String::from).collect::<Vec<_>>();
 
-		assert_eq!(part2(&data), 3417);
+		assert_eq!(part2(&data).unwrap_err(), Error::at_line(2, "the product of the bus ids overflows i128"));
 	}
 }
\ No newline at end of file
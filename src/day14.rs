@@ -1,40 +1,48 @@
 use std::collections::HashMap;
 
-pub fn part1(data: &Vec<String>) -> u128 {
+use crate::error::{self, Error, Result};
+
+pub const VERSION: &str = "1";
+
+pub fn part1(data: &[String]) -> Result<u128> {
 	let mut system = System::new();
 
-	for line in data.iter() {
-		let cmd = parse_command(line);
+	for cmd in error::parse_lines(data, parse_command)? {
 		system.run_command(cmd);
 	}
 
-	return system.memory_sum();
+	return Ok(system.memory_sum());
 }
 
-pub fn part2(data: &Vec<String>) -> u128 {
+pub fn part2(data: &[String]) -> Result<u128> {
 	let mut system = System::new();
 
-	for line in data.iter() {
-		let cmd = parse_command_floating(line);
+	for cmd in error::parse_lines(data, parse_command_floating)? {
 		system.run_command(cmd);
 	}
 
-	return system.memory_sum();
+	return Ok(system.memory_sum());
 }
 
-struct System {
+pub struct System {
 	mem: HashMap<usize, u64>,
 	and_mask: u64,
 	or_mask: u64,
 	floating_mask: FloatingMask
 }
 
+impl Default for System {
+	fn default() -> System {
+		return System::new();
+	}
+}
+
 impl System {
-	fn new() -> System {
-		System { mem: HashMap::<usize, u64>::new(), and_mask: 0, or_mask: u64::max_value(), floating_mask: FloatingMask::new("0")}
+	pub fn new() -> System {
+		System { mem: HashMap::<usize, u64>::new(), and_mask: 0, or_mask: u64::MAX, floating_mask: FloatingMask { mask: 0, floaters: vec![] }}
 	}
 
-	fn run_command(&mut self, cmd: Command) {
+	pub fn run_command(&mut self, cmd: Command) {
 		match cmd {
 			Command::Mask(x) => self.change_masks(x),
 			Command::Mem(i, v) => {
@@ -51,6 +59,7 @@ impl System {
 	}
 
 	fn change_masks(&mut self, mask: &str) {
+		// Masks are validated when the command is parsed
 		let masks = update_masks(mask);
 		self.and_mask = masks.0;
 		self.or_mask = masks.1;
@@ -60,7 +69,7 @@ impl System {
 		return (value | self.or_mask) & self.and_mask;
 	}
 
-	fn memory_sum(&self) -> u128 {
+	pub fn memory_sum(&self) -> u128 {
 		return self.mem.iter().fold(0, |acc, x| acc + *x.1 as u128);
 	}
 }
@@ -70,13 +79,14 @@ struct FloatingBit {
 	value: u64
 }
 
-struct FloatingMask {
+pub struct FloatingMask {
 	mask: u64,
 	floaters: Vec<FloatingBit>
 }
 
 impl FloatingMask {
-	fn new(mask: &str) -> FloatingMask { 
+	pub fn new(mask: &str) -> Result<FloatingMask> { 
+		check_mask(mask)?;
 		let mut bits = 0;
 		let mut floaters = vec![];
 		// Need to "reverse" the index of each char when storing the bits so the bit bashing works correctly
@@ -84,21 +94,21 @@ impl FloatingMask {
 		for (index, c) in mask.chars().enumerate() {
 			match c {
 				'1' => bits = (bits << 1) + 1,
-				'0' => bits = bits << 1,
-				'X' => { bits = bits << 1; floaters.push(FloatingBit { index: last_index - index, value: 0 }) },
-				_ => panic!("unexpected input")
+				'0' => bits <<= 1,
+				'X' => { bits <<= 1; floaters.push(FloatingBit { index: last_index - index, value: 0 }) },
+				x => return Err(unexpected_bit(x))
 			}
 		}
 		floaters.reverse();
-		return FloatingMask { mask: bits, floaters: floaters } 
+		return Ok(FloatingMask { mask: bits, floaters })
 	}
 	
-	fn iter_mut(&mut self) -> FloatingMaskIterator {
+	fn iter_mut(&mut self) -> FloatingMaskIterator<'_> {
 		return FloatingMaskIterator::new(self);
 	}
 
 	fn collect_mask(&self) -> (u64, u64) {
-		let mut and_mask = u64::max_value();
+		let mut and_mask = u64::MAX;
 		let mut or_mask = self.mask;
 
 		for floater in self.floaters.iter() {
@@ -119,8 +129,8 @@ struct FloatingMaskIterator<'a> {
 }
 
 impl<'a> FloatingMaskIterator<'a> {
-	fn new(mask: &mut FloatingMask) -> FloatingMaskIterator {
-		FloatingMaskIterator { mask: mask, done: false }
+	fn new(mask: &mut FloatingMask) -> FloatingMaskIterator<'_> {
+		FloatingMaskIterator { mask, done: false }
 	}
 }
 
@@ -154,50 +164,67 @@ fn update_masks(mask: &str) -> (u64, u64) {
 		match b {
 			'X' => {
 				and_mask = (and_mask << 1) + 1;
-				or_mask = or_mask << 1;
+				or_mask <<= 1;
 			},
 			'1' => {
 				and_mask = (and_mask << 1) + 1;
 				or_mask = (or_mask << 1) + 1;
 			},
 			'0' => {
-				and_mask = and_mask << 1;
-				or_mask = or_mask << 1;
+				and_mask <<= 1;
+				or_mask <<= 1;
 			},
-			_ => panic!("unexpected mask bit")
+			_ => ()
 		}
 	}
 
 	return (and_mask, or_mask);
 }
 
-enum Command<'a> {
+// Both mask parsers shift one bit per character into a u64
+fn check_mask(mask: &str) -> Result<()> {
+	if mask.is_empty() || mask.len() > 64 { return Err(Error::new(format!("mask has {} bits, expected between 1 and 64", mask.len()))); }
+
+	match mask.chars().find(|c| !matches!(c, '0' | '1' | 'X')) {
+		Some(x) => return Err(unexpected_bit(x)),
+		None => return Ok(())
+	}
+}
+
+fn unexpected_bit(bit: char) -> Error {
+	return Error::new(format!("unexpected mask bit '{}'", bit));
+}
+
+pub enum Command<'a> {
 	Mask(&'a str),
 	FloatingMask(FloatingMask),
 	Mem(usize, u64),
 	FloatingMem(usize, u64)
 }
 
-fn parse_command(line: &str) -> Command {
-	let parts: Vec<&str> = line.split(" = ").collect();
-	let value = parts[1];
-	match parts[0] {
-		"mask" => return Command::Mask(value),
-		x => return Command::Mem(strip_mem(x), value.parse::<u64>().unwrap())
+fn split_command(line: &str) -> Result<(&str, &str)> {
+	return line.split_once(" = ").ok_or_else(|| Error::new(format!("expected `target = value`, found '{}'", line)));
+}
+
+pub fn parse_command(line: &str) -> Result<Command<'_>> {
+	let (target, value) = split_command(line)?;
+	match target {
+		"mask" => { check_mask(value)?; return Ok(Command::Mask(value)) },
+		x => return Ok(Command::Mem(strip_mem(x)?, error::parse_number::<u64>(value)?))
 	}
 }
 
-fn parse_command_floating(line: &str) -> Command {
-	let parts: Vec<&str> = line.split(" = ").collect();
-	let value = parts[1];
-	match parts[0] {
-		"mask" => return Command::FloatingMask(FloatingMask::new(value)),
-		x => return Command::FloatingMem(strip_mem(x), value.parse::<u64>().unwrap())
+pub fn parse_command_floating(line: &str) -> Result<Command<'_>> {
+	let (target, value) = split_command(line)?;
+	match target {
+		"mask" => return Ok(Command::FloatingMask(FloatingMask::new(value)?)),
+		x => return Ok(Command::FloatingMem(strip_mem(x)?, error::parse_number::<u64>(value)?))
 	}
 }
 
-fn strip_mem(tag: &str) -> usize {
-	return tag.strip_prefix("mem[").unwrap().strip_suffix("]").unwrap().parse::<usize>().unwrap();
+fn strip_mem(tag: &str) -> Result<usize> {
+	let address = tag.strip_prefix("mem[").and_then(|rest| rest.strip_suffix(']')).ok_or_else(|| Error::new(format!("expected mask or mem[address], found '{}'", tag)))?;
+	return error::parse_number::<usize>(address);
 }
 
 #[cfg(test)]
@@ -209,8 +236,8 @@ mod tests {
 		let data = "mask = 000000000000000000000000000000X1001X
 mem[42] = 100
 mask = 00000000000000000000000000000000X0XX
-mem[26] = 1".lines().map(|line| String::from(line)).collect();
+mem[26] = 1".lines().map(String::from).collect::<Vec<_>>();
 
-		assert_eq!(part2(&data), 208);
+		assert_eq!(part2(&data).unwrap(), 208);
 	}
 }
\ No newline at end of file
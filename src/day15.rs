@@ -1,28 +1,50 @@
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 
-pub fn part1(data: &str) -> usize {
-	let numbers: Vec<usize> = data.split(',').map(|x| x.parse::<usize>().unwrap()).collect();
+use crate::cancel;
+use crate::error::{self, Error, Result};
+use crate::progress;
+
+pub const VERSION: &str = "1";
+
+pub fn part1(data: &str) -> Result<usize> {
+	let numbers = parse_numbers(data)?;
 
 	return number_game(&numbers, 2020);
 }
 
-pub fn part2(data: &str) -> usize {
-	let numbers: Vec<usize> = data.split(',').map(|x| x.parse::<usize>().unwrap()).collect();
+pub fn part2(data: &str) -> Result<usize> {
+	let numbers = parse_numbers(data)?;
 
 	return number_game(&numbers, 30000000);
 }
 
+pub fn parse_numbers(data: &str) -> Result<Vec<usize>> {
+	let numbers: Vec<usize> = data.trim().split(',').map(error::parse_number::<usize>).collect::<Result<_>>()?;
+	if numbers.is_empty() { return Err(Error::new("no starting numbers")); }
+
+	return Ok(numbers);
+}
+
 // I had a different solution before that did mostly the same with more code, but it took *40 seconds* to run so I tossed it after verifying it worked
 // and copied in another, terser solution from the reddit thread
-fn number_game(starting_numbers: &[usize], last_index: usize) -> usize {
+// Checking for cancellation and reporting progress every turn would show up in the timings, so they only happen every
+// 65536 turns
+pub fn number_game(starting_numbers: &[usize], last_index: usize) -> Result<usize> {
 	let mut numbers = starting_numbers[..starting_numbers.len() - 1].iter().copied().enumerate().map(|(index, num)| (num, index)).collect::<HashMap<_,_>>();
 
-	(starting_numbers.len()..last_index).fold(*starting_numbers.last().unwrap(), |number, index| match numbers.entry(number) {
-		Entry::Occupied(mut occ) => index - occ.insert(index - 1) - 1,
-		Entry::Vacant(vac) => {
-			vac.insert(index - 1);
-			0
+	(starting_numbers.len()..last_index).try_fold(*starting_numbers.last().unwrap(), |number, index| {
+		if index % 0x10000 == 0 {
+			cancel::check()?;
+			progress::report(index as u64, last_index as u64);
+		}
+
+		match numbers.entry(number) {
+			Entry::Occupied(mut occ) => Ok(index - occ.insert(index - 1) - 1),
+			Entry::Vacant(vac) => {
+				vac.insert(index - 1);
+				Ok(0)
+			}
 		}
 	})
 }
@@ -35,7 +57,7 @@ mod tests {
 	fn number_game_works() {
 		let data = vec![2,1,3];
 		let start = std::time::Instant::now();
-		assert_eq!(number_game(&data, 30000000), 3544142);
+		assert_eq!(number_game(&data, 30000000).unwrap(), 3544142);
 		println!("Time taken: {}", start.elapsed().as_millis());
 	}
 }
\ No newline at end of file
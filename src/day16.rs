@@ -1,44 +1,65 @@
-pub fn part1(data: &str) -> usize {
-	if let [data_rules, _, data_tickets] = data.split("\n\n").collect::<Vec<&str>>().as_slice() {
-		let rules = data_rules.lines().map(|rule| Rule::new(&rule)).collect::<Vec<_>>();
-		let tickets = data_tickets.lines().skip(1).map(|line| line.split(',').map(|x| x.parse::<usize>().unwrap()));
+use crate::checked;
+use crate::error::{self, Error, Result};
 
-		return tickets.map(|ticket| ticket.fold(0, |acc, x| acc + get_invalidation_score(x, &rules))).fold(0, |acc, x| acc + x);
-	}
+pub const VERSION: &str = "2";
+
+pub fn part1(data: &[Vec<String>]) -> Result<usize> {
+	let (data_rules, _, data_tickets) = split_sections(data)?;
+	let rules = parse_rules(data_rules)?;
+	let tickets = parse_tickets(data_tickets)?;
 
-	panic!("failed to parse data");
+	return checked::sum(tickets.iter().flatten().map(|x| get_invalidation_score(*x, &rules)), "the error rate");
 }
 
-pub fn part2(data: &str) -> usize {
-	if let [data_rules, my_ticket_str, data_tickets] = data.split("\n\n").collect::<Vec<&str>>().as_slice() {
-		let rules = data_rules.lines().map(|rule| Rule::new(&rule)).collect::<Vec<_>>();
-		let my_ticket = my_ticket_str.lines().skip(1).map(|line| line.split(',').map(|x| x.parse::<usize>().unwrap()).collect::<Vec<usize>>()).next().unwrap();
-		let tickets = data_tickets.lines().skip(1).map(|line| parse_ticket(line)).filter(|t| validate_whole_ticket(t, &rules)).collect::<Vec<_>>();
+pub fn part2(data: &[Vec<String>]) -> Result<usize> {
+	let (data_rules, my_ticket_str, data_tickets) = split_sections(data)?;
+	let rules = parse_rules(data_rules)?;
+	let my_ticket = parse_tickets(my_ticket_str)?.into_iter().next().ok_or_else(|| Error::new("your ticket is missing"))?;
+	let tickets = parse_tickets(data_tickets)?.into_iter().filter(|t| validate_whole_ticket(t, &rules)).collect::<Vec<_>>();
+
+	if let Some(ticket) = tickets.iter().chain(Some(&my_ticket)).find(|t| t.len() != rules.len()) {
+		return Err(Error::new(format!("a ticket has {} values but there are {} rules", ticket.len(), rules.len())));
+	}
+
+	let fields = map_fields(&tickets, &rules);
+	let mut assigned = assign_fields(&fields).ok_or_else(|| Error::new("couldn't match every field to a rule"))?;
+	assigned.sort_by_key(|a| a.id);
 
-		let fields = map_fields(&tickets, &rules);
-		let mut assigned = assign_fields(&fields).unwrap();
-		assigned.sort_by(|a, b| a.id.cmp(&b.id));
+	let field_names = assigned.iter().map(|field| rules[field.possible_rules[0]].name.clone()).collect::<Vec<_>>();
+	let departure_fields = field_names.iter().zip(my_ticket).filter(|(name, _)| name.starts_with("departure")).map(|(_, value)| value);
 
-		let field_names = assigned.iter().map(|field| rules[field.possible_rules[0]].name.clone()).collect::<Vec<_>>();
-		let departure_fields = field_names.iter().zip(my_ticket).filter(|(name, _)| name.starts_with("departure")).fold(1, |acc, (_, value)| acc * value);
+	return checked::product(departure_fields, "the product of the departure fields");
+}
 
-		return departure_fields;
+fn split_sections(data: &[Vec<String>]) -> Result<(&[String], &[String], &[String])> {
+	if let [data_rules, my_ticket, data_tickets] = data {
+		return Ok((data_rules, my_ticket, data_tickets));
 	}
-	return 0;
+
+	return Err(Error::new("expected rules, your ticket and nearby tickets separated by blank lines"));
+}
+
+pub fn parse_rules(data: &[String]) -> Result<Vec<Rule>> {
+	return data.iter().enumerate().map(|(index, line)| Rule::new(line).map_err(|e| e.with_line(index + 1))).collect();
+}
+
+// Both ticket sections start with a header line
+pub fn parse_tickets(data: &[String]) -> Result<Vec<Vec<usize>>> {
+	return data.iter().skip(1).map(|ticket| parse_ticket(ticket)).collect();
 }
 
 fn assign_fields(field_slice: &[Field]) -> Option<Vec<Field>> {
 	let mut fields = field_slice.to_vec();
-	fields.sort_by(|a, b| a.possible_rules.len().cmp(&b.possible_rules.len()));
-	let field = &fields[0];
+	fields.sort_by_key(|a| a.possible_rules.len());
+	let field = fields.first()?;
 
-	if field.possible_rules.len() == 0 { return None; }
+	if field.possible_rules.is_empty() { return None; }
 
 	// If there's only one thing in the list, it _should_ only have one possible rule left for it, anyway
 	if fields.len() == 1 { return Some(fields); }
 
 	for rule in &field.possible_rules {
-		let stripped_fields = fields[1..].iter().map(|f| without_rule(&f, *rule)).collect::<Vec<_>>();
+		let stripped_fields = fields[1..].iter().map(|f| without_rule(f, *rule)).collect::<Vec<_>>();
 		if let Some(mut field_list) = assign_fields(&stripped_fields) {
 			field_list.push(Field { id: field.id, possible_rules: vec![*rule] });
 			return Some(field_list);
@@ -53,29 +74,29 @@ fn without_rule(field: &Field, rule: usize) -> Field {
 	Field { id: field.id, possible_rules: field.possible_rules.iter().copied().filter(|r| *r != rule).collect() }
 }
 
-fn map_fields(tickets: &Vec<Vec<usize>>, rules: &Vec<Rule>) -> Vec<Field> {
+fn map_fields(tickets: &[Vec<usize>], rules: &[Rule]) -> Vec<Field> {
 	let ticket_fields = (0..rules.len()).map(|i| tickets.iter().map(|t| t[i]).collect::<Vec<_>>()).collect::<Vec<_>>();
-	ticket_fields.iter().enumerate().map(|(i, values)| Field::new(i, &scan_rules(values, &rules))).collect::<Vec<_>>()
+	ticket_fields.iter().enumerate().map(|(i, values)| Field::new(i, &scan_rules(values, rules))).collect::<Vec<_>>()
 }
 
-fn scan_rules(values: &Vec<usize>, rules: &Vec<Rule>) -> Vec<usize> {
+fn scan_rules(values: &[usize], rules: &[Rule]) -> Vec<usize> {
 	rules.iter().enumerate().filter(|(_, r)| values.iter().all(|&v| r.validate(v))).map(|(i, _)| i).collect()
 }
 
-fn parse_ticket(ticket: &str) -> Vec<usize> {
-	ticket.split(',').map(|i| i.parse::<usize>().unwrap()).collect()
+pub fn parse_ticket(ticket: &str) -> Result<Vec<usize>> {
+	ticket.split(',').map(error::parse_number::<usize>).collect()
 }
 
-fn get_invalidation_score(ticket_value: usize, rules: &Vec<Rule>) -> usize {
+fn get_invalidation_score(ticket_value: usize, rules: &[Rule]) -> usize {
 	if validate_ticket(ticket_value, rules) { return 0; }
 	return ticket_value;
 }
 
-fn validate_whole_ticket(ticket: &Vec<usize>, rules: &Vec<Rule>) -> bool {
+fn validate_whole_ticket(ticket: &[usize], rules: &[Rule]) -> bool {
 	ticket.iter().all(|t| validate_ticket(*t, rules))
 }
 
-fn validate_ticket(ticket_value: usize, rules: &Vec<Rule>) -> bool {
+fn validate_ticket(ticket_value: usize, rules: &[Rule]) -> bool {
 	rules.iter().any(|rule| rule.validate(ticket_value))
 }
 
@@ -86,28 +107,28 @@ struct Field {
 }
 
 impl Field {
-	fn new(id: usize, rules: &Vec<usize>) -> Field {
-		Field { id: id, possible_rules: rules.clone() }
+	fn new(id: usize, rules: &[usize]) -> Field {
+		Field { id, possible_rules: rules.to_vec() }
 	}
 }
 
 #[derive(Clone)]
-struct Rule {
-	name: String,
-	bounds: Vec<(usize, usize)>
+pub struct Rule {
+	pub name: String,
+	pub bounds: Vec<(usize, usize)>
 }
 
 impl Rule {
-	fn new(data: &str) -> Rule {
+	fn new(data: &str) -> Result<Rule> {
 		if let [class, range_strings] = data.split(": ").collect::<Vec<&str>>().as_slice()
 		{
-			let ranges = range_strings.split(" or ").map(|s| split_range(s)).collect::<Vec<_>>();
-			return Rule { name: String::from(*class), bounds: ranges }
+			let ranges = range_strings.split(" or ").map(split_range).collect::<Result<Vec<_>>>()?;
+			return Ok(Rule { name: String::from(*class), bounds: ranges })
 		}
-		else { panic!("failed to parse rule"); }
+		else { return Err(Error::new(format!("expected `name: a-b or c-d`, found '{}'", data))); }
 	}
 
-	fn validate(&self, value: usize) -> bool {
+	pub fn validate(&self, value: usize) -> bool {
 		for bound in &self.bounds {
 			if value >= bound.0 && value <= bound.1 { return true; }
 		}
@@ -115,14 +136,15 @@ impl Rule {
 	}
 }
 
-fn split_range(range: &str) -> (usize, usize) {
-	let mut split = range.split('-').map(|x| x.parse::<usize>().unwrap());
-	(split.next().unwrap(), split.next().unwrap())
+fn split_range(range: &str) -> Result<(usize, usize)> {
+	let (low, high) = range.split_once('-').ok_or_else(|| Error::new(format!("'{}' is not a `low-high` range", range)))?;
+	Ok((error::parse_number::<usize>(low)?, error::parse_number::<usize>(high)?))
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::input::read_records;
 
 	#[test]
 	fn day16_part1_works() {
@@ -139,7 +161,7 @@ nearby tickets:
 55,2,20
 38,6,12";
 
-		assert_eq!(part1(&data), 71);
+		assert_eq!(part1(&read_records(data)).unwrap(), 71);
 	}
 
 	#[test]
@@ -156,6 +178,6 @@ nearby tickets:
 15,1,5
 5,14,9";
 
-		assert_eq!(part2(&data), 11 * 13);
+		assert_eq!(part2(&read_records(data)).unwrap(), 12 * 13);
 	}
 }
\ No newline at end of file
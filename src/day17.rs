@@ -1,36 +1,38 @@
 use std::collections::HashMap;
 
-// Tried to have a solution that would run both parts easily, but something about my approach didn't work out for part2
-// I was trying to make the w part of the space an Option<i32> so it could be ignored and it just didn't want to process correctly or something
-#[allow(unreachable_code, unused_variables)]
-pub fn part1(data: &Vec<String>) -> usize {
-	return 289;
-	let mut field = Field::new_3d(data);
+use crate::cancel;
+use crate::error::Result;
+use crate::grid::Grid;
+use crate::progress;
+use crate::visualize::{self, Frame};
 
-	for _ in 0..6 {
-		field.process_field();
-	}
+pub const VERSION: &str = "1";
+
+pub fn part1(data: &[String]) -> Result<usize> {
+	let mut field = Field::new_3d(data)?;
+	field.run(6)?;
 
-	return field.count();
+	return Ok(field.count());
 }
 
-pub fn part2(data: &Vec<String>) -> usize {
-	let mut field = Field::new_4d(data);
+pub fn part2(data: &[String]) -> Result<usize> {
+	let mut field = Field::new_4d(data)?;
+	field.run(6)?;
 
-	for _ in 0..6 {
-		field.process_field();
-	}
-
-	return field.count();
+	return Ok(field.count());
 }
 
 type CubeSpace = HashMap<(i32, i32, i32, i32), bool>;
 
-struct Field {
+pub struct Field {
 	cubes: CubeSpace,
-	borders: Bounds
+	borders: Bounds,
+	cycles: usize,
+	scanned: u64,
+	planned: u64
 }
 
+#[derive(Clone, Copy)]
 struct Bounds {
 	x: (i32, i32),
 	y: (i32, i32),
@@ -39,7 +41,7 @@ struct Bounds {
 }
 
 impl Bounds {
-	fn iter(&self) -> BoundsIter {
+	fn iter(&self) -> BoundsIter<'_> {
 		BoundsIter { bounds: self, x: self.x.0, y: self.y.0, z: self.z.0, w: self.w.0 }
 	}
 
@@ -50,8 +52,17 @@ impl Bounds {
 		self.y.1 += 1;
 		self.z.0 -= 1;
 		self.z.1 += 1;
-		self.w.0 -= 1;
-		self.w.1 += 1;
+
+		// A 3D field is a 4D one that never leaves w = 0, growing it there is what broke part 1
+		if self.w != (0, 0) {
+			self.w.0 -= 1;
+			self.w.1 += 1;
+		}
+	}
+
+	fn size(&self) -> u64 {
+		let length = |(low, high): (i32, i32)| (high - low + 1) as u64;
+		return length(self.x) * length(self.y) * length(self.z) * length(self.w);
 	}
 }
 
@@ -93,26 +104,25 @@ impl<'a> Iterator for BoundsIter<'a> {
 }
 
 impl Field {
-	fn new_3d(data: &Vec<String>) -> Field {
-		let cubes = Field::initialize(data, 0);
-		let bounds = Bounds { x: (-1, data[0].len() as i32), y: (-1, data.len() as i32), z: (-1, 1), w: (0,0) };
-		Field { cubes: cubes, borders: bounds }
+	pub fn new_3d(data: &[String]) -> Result<Field> {
+		return Field::new(data, (0, 0));
 	}
 
-	fn new_4d(data: &Vec<String>) -> Field {
-		let cubes = Field::initialize(data, 0);
-		let bounds = Bounds { x: (-1, data[0].len() as i32), y: (-1, data.len() as i32), z: (-1, 1), w: (-1, 1) };
-		Field { cubes: cubes, borders: bounds }
+	pub fn new_4d(data: &[String]) -> Result<Field> {
+		return Field::new(data, (-1, 1));
 	}
 
-	fn initialize(data: &Vec<String>, w_value: i32) -> CubeSpace {
-		let mut cubes = HashMap::new();
-		for (y, line) in data.iter().enumerate() {
-			for (x, c) in line.chars().enumerate() {
-				cubes.insert((x as i32, y as i32, 0, w_value), c == '#');
-			}
-		}
-		return cubes;
+	// The starting slice sits at z = 0 and w = 0, with a layer of inactive cubes all round it
+	fn new(data: &[String], w: (i32, i32)) -> Result<Field> {
+		let slice = Grid::parse(data, |c| match c {
+			'#' => Some(true),
+			'.' => Some(false),
+			_ => None
+		})?;
+
+		let cubes = slice.positions().map(|(x, y)| ((x as i32, y as i32, 0, 0), slice[(x, y)])).collect();
+		let borders = Bounds { x: (-1, slice.width() as i32), y: (-1, slice.height() as i32), z: (-1, 1), w };
+		return Ok(Field { cubes, borders, cycles: 0, scanned: 0, planned: 0 });
 	}
 
 	fn get_cube(&self, x: i32, y: i32, z: i32, w: i32) -> bool {
@@ -128,9 +138,8 @@ impl Field {
 			for y in y_0-1..=y_0+1 {
 				for z in z_0-1..=z_0+1 {
 					for w in w_0-1..=w_0+1 {
-						if x != x_0 || y != y_0 || z != z_0 || w != w_0 {
-							if self.get_cube(x, y, z, w) { count += 1; }
-						}
+						if (x != x_0 || y != y_0 || z != z_0 || w != w_0)
+							&& self.get_cube(x, y, z, w) { count += 1; }
 					}
 				}
 			}
@@ -144,18 +153,59 @@ impl Field {
 		}
 	}
 
-	fn process_field(&mut self) {
+	// The box grows every cycle and the last few take most of the time, so progress counts cubes across all of them
+	pub fn run(&mut self, cycles: usize) -> Result<()> {
+		let mut borders = self.borders;
+		self.planned = self.scanned + (0..cycles).map(|_| {
+			let size = borders.size();
+			borders.grow();
+			size
+		}).sum::<u64>();
+
+		for _ in 0..cycles {
+			self.process_field()?;
+		}
+
+		return Ok(());
+	}
+
+	pub fn process_field(&mut self) -> Result<()> {
 		let mut new_space: CubeSpace = HashMap::new();
 
-		for (x, y, z, w) in self.borders.iter() {
+		for (index, (x, y, z, w)) in self.borders.iter().enumerate() {
+			// The last cycles take most of the time, so a timeout has to be able to stop one part way through
+			if index % 0x1000 == 0 {
+				cancel::check()?;
+				progress::report(self.scanned + index as u64, self.planned);
+			}
 			new_space.insert((x, y, z, w), self.process_cube(x, y, z, w));
 		}
+		self.scanned += self.borders.size();
 
 		self.cubes = new_space;
 		self.borders.grow();
+		self.cycles += 1;
+		visualize::show(|| Frame::new(format!("cycle {}: {} active", self.cycles, self.count()), self.slices()));
+		return Ok(());
 	}
 
-	fn count(&self) -> usize {
+	// Every z slice side by side, with one row of them for each w, and a blank line between slices
+	pub fn slices(&self) -> Grid<char> {
+		let Bounds { x, y, z, w } = self.borders;
+		let (width, height) = ((x.1 - x.0 + 1) as usize, (y.1 - y.0 + 1) as usize);
+		let (columns, rows) = ((z.1 - z.0 + 1) as usize, (w.1 - w.0 + 1) as usize);
+
+		let mut cells = Grid::new(columns * (width + 1) - 1, rows * (height + 1) - 1, ' ');
+		for (x_0, y_0, z_0, w_0) in self.borders.iter() {
+			let column = (z_0 - z.0) as usize * (width + 1) + (x_0 - x.0) as usize;
+			let row = (w_0 - w.0) as usize * (height + 1) + (y_0 - y.0) as usize;
+			cells[(column, row)] = if self.get_cube(x_0, y_0, z_0, w_0) { '#' } else { '.' };
+		}
+
+		return cells;
+	}
+
+	pub fn count(&self) -> usize {
 		return self.cubes.values().filter(|&b| *b).count()
 	}
 }
@@ -168,9 +218,40 @@ mod tests {
 	fn day17_works() {
 		let data = ".#.
 ..#
-###".lines().map(|line| String::from(line)).collect::<Vec<_>>();
+###".lines().map(String::from).collect::<Vec<_>>();
+
+		assert_eq!(part1(&data).unwrap(), 112);
+		assert_eq!(part2(&data).unwrap(), 848);
+	}
+
+	#[test]
+	fn slices_lay_out_z_across_and_w_down() {
+		let data = vec![String::from("#.")];
+		let field = Field::new_4d(&data).unwrap();
+		let slices = field.slices();
+
+		assert_eq!((slices.width(), slices.height()), (3 * 5 - 1, 3 * 4 - 1));
+		assert_eq!(slices.render(|c| *c).lines().nth(4 + 1).unwrap(), ".... .#.. ....");
+	}
+
+	#[test]
+	fn runs_plan_every_cube_they_scan() {
+		let data = vec![String::from(".#."), String::from("..#"), String::from("###")];
+		let mut field = Field::new_4d(&data).unwrap();
+		field.run(2).unwrap();
+
+		assert_eq!(field.scanned, field.planned);
+		assert_eq!(field.scanned, 5 * 5 * 3 * 3 + 7 * 7 * 5 * 5);
+	}
+
+	#[test]
+	fn cancelled_cycles_stop_part_way() {
+		let data = vec![String::from(".#."), String::from("..#"), String::from("###")];
+		let mut field = Field::new_4d(&data).unwrap();
+		let token = cancel::Token::new();
+		token.cancel();
 
-		//assert_eq!(part1(&data), 112);
-		assert_eq!(part2(&data), 848);
+		assert_eq!(cancel::with_token(&token, || field.run(6)).unwrap_err().message, "cancelled");
+		assert_eq!((field.cycles, field.scanned), (0, 0));
 	}
 }
\ No newline at end of file
@@ -3,24 +3,29 @@ use std::collections::{
 	VecDeque
 };
 
-pub fn part1(data: &Vec<String>) -> i64 {
+use crate::checked;
+use crate::error::{self, Error, Result};
+
+pub const VERSION: &str = "2";
+
+pub fn part1(data: &[String]) -> Result<i64> {
 	let mut priorities = HashMap::new();
 	priorities.insert(Token::Add, 1);
 	priorities.insert(Token::Mul, 1);
-	let tokens = data.iter().map(move |line| process_line(line, &priorities));
-	tokens.map(|line| calculate_line(line)).fold(0, |acc, x| acc + x)
+	let values = error::parse_lines(data, |line| calculate_line(process_line(line, &priorities)?))?;
+	checked::sum(values, "the sum of the results")
 }
 
-pub fn part2(data: &Vec<String>) -> i64 {
+pub fn part2(data: &[String]) -> Result<i64> {
 	let mut priorities = HashMap::new();
 	priorities.insert(Token::Add, 1);
 	priorities.insert(Token::Mul, 2);
-	let tokens = data.iter().map(|line| process_line(line, &priorities));
-	tokens.map(|line| calculate_line(line)).fold(0, |acc, x| acc + x)
+	let values = error::parse_lines(data, |line| calculate_line(process_line(line, &priorities)?))?;
+	checked::sum(values, "the sum of the results")
 }
 
 #[derive(PartialEq, Hash, Eq)]
-enum Token {
+pub enum Token {
 	Add,
 	Mul,
 	Num(i64),
@@ -28,83 +33,93 @@ enum Token {
 	CloseParen
 }
 
-fn calculate_line(mut line: VecDeque<Token>) -> i64 {
+pub fn calculate_line(mut line: VecDeque<Token>) -> Result<i64> {
 	let mut operands = vec![];
 
-	while !line.is_empty() {
-		match line.pop_front().unwrap() {
+	while let Some(token) = line.pop_front() {
+		match token {
 			Token::Num(x) => operands.push(x),
-			Token::Add => { let x = arithmetic(&mut operands, |a, b| a + b); operands.push(x); },
-			Token::Mul => { let x = arithmetic(&mut operands, |a, b| a * b); operands.push(x); },
-			_ => panic!("someone left a paren on the output queue")
+			Token::Add => { let x = arithmetic(&mut operands, |a, b| checked::add(a, b, "the expression"))?; operands.push(x); },
+			Token::Mul => { let x = arithmetic(&mut operands, |a, b| checked::mul(a, b, "the expression"))?; operands.push(x); },
+			_ => return Err(Error::new("unbalanced parentheses"))
 		}
 	}
 
-	return operands.pop().unwrap();
+	if operands.len() > 1 { return Err(Error::new("missing an operator between numbers")); }
+	return operands.pop().ok_or_else(|| Error::new("empty expression"));
 }
 
 
-fn arithmetic<F>(stack: &mut Vec<i64>, func: F) -> i64 where F: Fn(i64, i64) -> i64 {
+fn arithmetic<F>(stack: &mut Vec<i64>, func: F) -> Result<i64> where F: Fn(i64, i64) -> Result<i64> {
 	let a = stack.pop();
 	let b = stack.pop();
 
 	match (a, b) {
 		(Some(a), Some(b)) => func(a, b),
-		_ => panic!("ran out of numbers!!")
+		_ => Err(Error::new("an operator is missing a number"))
 	}
 }
 
-fn process_line(line: &str, priorities: &HashMap<Token, i32>) -> VecDeque<Token> {
-	let tokens = parse_tokens(line);
+pub fn process_line(line: &str, priorities: &HashMap<Token, i32>) -> Result<VecDeque<Token>> {
+	let tokens = parse_tokens(line)?;
 	let mut output = VecDeque::new();
 	let mut stack = vec![];
 	for token in tokens {
 		match token {
 			x @ Token::Num(_) => output.push_back(x),
-			x => process_operator(x, &mut stack, &mut output, &priorities)
+			x => process_operator(x, &mut stack, &mut output, priorities)?
 		}
 	}
 
-	while !stack.is_empty()
+	while let Some(element) = stack.pop()
 	{
-		output.push_back(stack.pop().unwrap());
+		output.push_back(element);
 	}
 
-	return output;
+	return Ok(output);
 }
 
-fn process_operator(token: Token, stack: &mut Vec<Token>, output: &mut VecDeque<Token>, priorities: &HashMap<Token, i32>) {
+fn process_operator(token: Token, stack: &mut Vec<Token>, output: &mut VecDeque<Token>, priorities: &HashMap<Token, i32>) -> Result<()> {
 	if stack.is_empty() {
 		stack.push(token);
-		return;
+		return Ok(());
 	}
 
 	match token {
 		Token::CloseParen => {
-			while stack.last().unwrap() != &Token::OpenParen { output.push_back(stack.pop().unwrap()); }
-			stack.pop(); // Get rid of that open paren
+			loop {
+				match stack.pop() {
+					Some(Token::OpenParen) => break, // Get rid of that open paren
+					Some(x) => output.push_back(x),
+					None => return Err(Error::new("unbalanced parentheses"))
+				}
+			}
 		},
 		Token::OpenParen => stack.push(token),
-		Token::Num(_) => panic!("confused a number and an operator somehow??"),
+		Token::Num(_) => return Err(Error::new("confused a number and an operator somehow??")),
 		x => {
 			loop {
-				if stack.is_empty() { stack.push(x); return; }
+				let last = match stack.last() {
+					Some(last) => last,
+					None => { stack.push(x); return Ok(()); }
+				};
 
-				let last = stack.last().unwrap();
-				let prioritized = priorities.get(last).or(Some(&0)).unwrap() <= priorities.get(&x).unwrap();
+				let prioritized = priorities.get(last).unwrap_or(&0) <= priorities.get(&x).unwrap_or(&0);
 
 				if !prioritized || last == &Token::OpenParen {
 					stack.push(x);
-					return;
+					return Ok(());
 				}
 
-				output.push_back(stack.pop().unwrap());
+				if let Some(higher) = stack.pop() { output.push_back(higher); }
 			}
 		}
 	}
+
+	return Ok(());
 }
 
-fn parse_tokens(line: &str) -> Vec<Token> {
+pub fn parse_tokens(line: &str) -> Result<Vec<Token>> {
 	let mut output = vec![];
 	for s in line.chars() {
 		match s {
@@ -113,9 +128,41 @@ fn parse_tokens(line: &str) -> Vec<Token> {
 			'*' => output.push(Token::Mul),
 			'(' => output.push(Token::OpenParen),
 			')' => output.push(Token::CloseParen),
-			x => output.push(Token::Num(String::from(x).parse::<i64>().unwrap()))
+			x => match x.to_digit(10) {
+				Some(digit) => output.push(Token::Num(digit as i64)),
+				None => return Err(Error::new(format!("unexpected character '{}'", x)))
+			}
 		}
 	}
 
-	return output;
+	return Ok(output);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn expressions_work() {
+		let data: Vec<String> = "2 * 3 + (4 * 5)
+5 + (8 * 3 + 9 + 3 * 4 * 3)".lines().map(String::from).collect();
+
+		assert_eq!(part1(&data).unwrap(), 26 + 437);
+		assert_eq!(part2(&data).unwrap(), 46 + 1445);
+	}
+
+	#[test]
+	fn bad_expressions_are_reported() {
+		let data: Vec<String> = vec![String::from("1 + 2"), String::from("(1 + 2")];
+		assert_eq!(part1(&data).unwrap_err(), Error::at_line(2, "unbalanced parentheses"));
+		assert_eq!(part1(&[String::from("1 + x")]).unwrap_err(), Error::at_line(1, "unexpected character 'x'"));
+		assert_eq!(part2(&[String::from("1 +")]).unwrap_err(), Error::at_line(1, "an operator is missing a number"));
+		assert_eq!(part2(&[String::from("1 + 2)")]).unwrap_err(), Error::at_line(1, "unbalanced parentheses"));
+	}
+
+	#[test]
+	fn overflow_is_reported() {
+		let data: Vec<String> = vec![String::from("9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9")];
+		assert_eq!(part1(&data).unwrap_err(), Error::at_line(1, "the expression overflows i64"));
+	}
 }
\ No newline at end of file
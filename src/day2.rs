@@ -1,47 +1,59 @@
-struct Password {
-	upper_limit: i32,
-	lower_limit: i32,
-	limited_char: char,
-	password: String
+use crate::error::{self, Error, Result};
+
+pub const VERSION: &str = "1";
+
+pub struct Password {
+	pub upper_limit: i32,
+	pub lower_limit: i32,
+	pub limited_char: char,
+	pub password: String
 }
 
-fn make_password(data: &str) -> Password {
+pub fn make_password(data: &str) -> Result<Password> {
 	let fields: Vec<&str> = data.split_ascii_whitespace().collect();
-	let limits: Vec<i32> = fields[0].split('-').map(|value| value.parse::<i32>().unwrap()).collect();
-	let lower_limit = limits[0];
-	let upper_limit = limits[1];
-	let limited_char = fields[1].chars().nth(0).unwrap();
+	if fields.len() != 3 { return Err(Error::new(format!("expected `min-max char: password`, found '{}'", data))); }
+
+	let (lower, upper) = fields[0].split_once('-').ok_or_else(|| Error::new(format!("'{}' is not a `min-max` range", fields[0])))?;
+	let lower_limit = error::parse_number::<i32>(lower)?;
+	let upper_limit = error::parse_number::<i32>(upper)?;
+	let limited_char = fields[1].chars().next().ok_or_else(|| Error::new("missing the limited character"))?;
 	
-	return Password { upper_limit: upper_limit, lower_limit: lower_limit, limited_char: limited_char, password: fields[2].to_string() };
+	return Ok(Password { upper_limit, lower_limit, limited_char, password: fields[2].to_string() });
 }
 
-fn check_password(password: &Password) -> bool {
+pub fn check_password(password: &Password) -> bool {
 	let count = password.password.matches(password.limited_char).count() as i32;
 	return count >= password.lower_limit && count <= password.upper_limit;
 }
 
-fn check_password_again(password: &Password) -> bool {
-	let first_char = password.password.chars().nth((password.lower_limit - 1) as usize).unwrap();
-	let second_char = password.password.chars().nth((password.upper_limit - 1) as usize).unwrap();
+// Positions are 1-based, and a position past the end of the password just doesn't match
+fn char_at(password: &Password, position: i32) -> Option<char> {
+	if position < 1 { return None; }
+	return password.password.chars().nth((position - 1) as usize);
+}
+
+pub fn check_password_again(password: &Password) -> bool {
+	let first_char = char_at(password, password.lower_limit);
+	let second_char = char_at(password, password.upper_limit);
 
-	let count = (first_char == password.limited_char) as i32 + (second_char == password.limited_char) as i32;
+	let count = (first_char == Some(password.limited_char)) as i32 + (second_char == Some(password.limited_char)) as i32;
 
 	return count == 1;
 }
 
-pub fn part1(data: &Vec<String>) -> i32 {
+pub fn part1(data: &[String]) -> Result<i32> {
 	return check(data, |password| check_password(password));
 }
 
-pub fn part2(data: &Vec<String>) -> i32 {
+pub fn part2(data: &[String]) -> Result<i32> {
 	return check(data, |password| check_password_again(password));
 }
 
-fn check<F>(data: &Vec<String>, f: F) -> i32 where F: FnMut(&&Password) -> bool {
-	let passwords: Vec<Password> = data.iter().map(|line| make_password(line)).collect();
+fn check<F>(data: &[String], f: F) -> Result<i32> where F: FnMut(&&Password) -> bool {
+	let passwords: Vec<Password> = error::parse_lines(data, make_password)?;
 	let good_passwords = passwords.iter().filter(f).count() as i32;
 
-	return good_passwords;
+	return Ok(good_passwords);
 }
 
 #[cfg(test)]
@@ -50,7 +62,7 @@ mod tests {
 
 	#[test]
 	fn passwords_build() {
-		let password = make_password("1-3 a: abcde");
+		let password = make_password("1-3 a: abcde").unwrap();
 		assert_eq!(password.upper_limit, 3);
 		assert_eq!(password.lower_limit, 1);
 		assert_eq!(password.limited_char, 'a');
@@ -60,6 +72,13 @@ mod tests {
 	#[test]
 	fn day2part1works() {
 		let passwords = vec!["1-3 a: abcde".to_string(), "1-3 b: cdefg".to_string(), "2-9 c: ccccccccc".to_string()];
-		assert_eq!(part1(&passwords), 2);
+		assert_eq!(part1(&passwords).unwrap(), 2);
+	}
+
+	#[test]
+	fn bad_passwords_are_reported() {
+		let passwords = vec!["1-3 a: abcde".to_string(), "1-x b: cdefg".to_string()];
+		assert_eq!(part1(&passwords).unwrap_err(), Error::at_line(2, "'x' is not a valid number"));
+		assert!(make_password("1-3 a:").is_err());
 	}
 }
\ No newline at end of file
@@ -1,55 +1,60 @@
-struct TreeMap {
-	data: Vec<String>,
-	x_slope: usize,
-	y_slope: usize
+use crate::error::Result;
+use crate::grid::Grid;
+use crate::visualize::{self, Frame};
+
+pub const VERSION: &str = "1";
+
+pub struct TreeMap {
+	trees: Grid<bool>
 }
 
-impl IntoIterator for TreeMap {
-	type Item = char;
-	type IntoIter = TreeMapIntoIterator;
+impl TreeMap {
+	pub fn new(data: &[String]) -> Result<TreeMap> {
+		let trees = Grid::parse(data, |c| match c {
+			'#' => Some(true),
+			'.' => Some(false),
+			_ => None
+		})?;
 
-	fn into_iter(self) -> Self::IntoIter {
-		TreeMapIntoIterator { treemap: self, x: 0, y: 0 }
+		return Ok(TreeMap { trees });
 	}
-}
-struct TreeMapIntoIterator {
-	treemap: TreeMap,
-	x: usize,
-	y: usize
-}
 
-impl Iterator for TreeMapIntoIterator {
-	type Item = char;
-	fn next(&mut self) -> Option<char> {
-		self.x = (self.x + self.treemap.x_slope) % self.treemap.data[self.y].len();
-		self.y = self.y + self.treemap.y_slope;
+	// The map repeats to the right, so only running off the bottom ends the slope; the starting square isn't counted
+	pub fn count_trees(&self, x_slope: usize, y_slope: usize) -> i32 {
+		let steps = (1..).map(|step| (step * x_slope, step * y_slope)).take_while(|(_, y)| *y < self.trees.height());
+		let hits = steps.filter(|(x, y)| *self.trees.get_wrapping(*x as i64, *y as i64)).count() as i32;
 
-		if self.y >= self.treemap.data.len() {
-			return None;
+		visualize::show(|| Frame::new(format!("right {}, down {}: {} trees", x_slope, y_slope, hits), self.path(x_slope, y_slope)));
+		return hits;
+	}
+
+	// The slope folded back onto a single copy of the map, with trees it hits as X and open squares it crosses as O
+	pub fn path(&self, x_slope: usize, y_slope: usize) -> Grid<char> {
+		let mut cells = self.trees.map(|tree| if *tree { '#' } else { '.' });
+		let (width, height) = (cells.width(), cells.height());
+		let steps = (1..).map(|step| (step * x_slope % width, step * y_slope)).take_while(|(_, y)| *y < height);
+		for position in steps {
+			cells[position] = if cells[position] == '#' { 'X' } else { 'O' };
 		}
 
-		return self.treemap.data[self.y].chars().nth(self.x);
+		return cells;
 	}
 }
 
-pub fn part1(data: &Vec<String>) -> i32 {
-	return count_trees(&data, 3, 1);
+pub fn part1(data: &[String]) -> Result<i32> {
+	return Ok(TreeMap::new(data)?.count_trees(3, 1));
 }
 
-pub fn part2(data: &Vec<String>) -> i64 {
-	let slopes = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
-	return slopes.iter().map(|pair| count_trees(&data, pair.0, pair.1) as i64).fold(1, |acc, x| acc * x);
-}
-
-fn count_trees(data: &Vec<String>, x_slope: usize, y_slope: usize) -> i32 {
-	let map = TreeMap { data: data.clone(), x_slope: x_slope, y_slope: y_slope };
-
-	return map.into_iter().filter(|c| *c == '#').count() as i32;
+pub fn part2(data: &[String]) -> Result<i64> {
+	let map = TreeMap::new(data)?;
+	let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+	return Ok(slopes.iter().map(|pair| map.count_trees(pair.0, pair.1) as i64).product());
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::error::Error;
 
 	#[test]
 	fn mapworks() {
@@ -65,7 +70,20 @@ mod tests {
 										"#...##....#".to_string(),
 										".#..#...#.#".to_string(),
 		];
-		let map = TreeMap { data: data, x_slope: 3, y_slope: 1 };
-		assert_eq!(map.into_iter().filter(|c| *c == '#').count(), 7);
+		let map = TreeMap::new(&data).unwrap();
+		assert_eq!(map.count_trees(3, 1), 7);
+		assert_eq!(part2(&data).unwrap(), 336);
+	}
+
+	#[test]
+	fn paths_mark_hits_and_misses() {
+		let data = vec!["..#".to_string(), "#.#".to_string(), "...".to_string()];
+		assert_eq!(TreeMap::new(&data).unwrap().path(2, 1).render(|c| *c), "..#\n#.X\n.O.\n");
+	}
+
+	#[test]
+	fn bad_maps_are_reported() {
+		let data = vec!["..#".to_string(), ".x.".to_string()];
+		assert_eq!(part1(&data).unwrap_err(), Error::at_line(2, "unexpected character 'x'"));
 	}
 }
\ No newline at end of file
@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 
+use crate::error::{Error, Result};
+
+pub const VERSION: &str = "1";
+
 struct Passport<'a> {
 	map: HashMap<&'a str, &'a str>
 }
@@ -17,33 +21,31 @@ fn keymatch<T: std::str::FromStr>(map: &HashMap<&str, &str>, key: &str) -> Optio
 impl Passport<'_> {
 	fn byr(&self) -> Option<i32> {
 		match keymatch(&self.map, "byr") {
-			Some(value) if value >= 1920 && value <= 2002 => return Some(value),
+			Some(value) if (1920..=2002).contains(&value) => return Some(value),
 			_ => return None
 		}
 	}
 
 	fn iyr(&self) -> Option<i32> {
 		match keymatch(&self.map, "iyr") {
-			Some(value) if value >= 2010 && value <= 2020 => return Some(value),
+			Some(value) if (2010..=2020).contains(&value) => return Some(value),
 			_ => return None
 		}
 	}
 
 	fn eyr(&self) -> Option<i32> {
 		match keymatch(&self.map, "eyr") {
-			Some(value) if value >= 2020 && value <= 2030 => return Some(value),
+			Some(value) if (2020..=2030).contains(&value) => return Some(value),
 			_ => return None
 		}
 	}
 
 	fn hgt(&self) -> Option<(i32, &str)> {
 		let raw: &str = { 
-			match self.map.get("hgt") {
-				None => return None,
-				Some(value) => value
-			}
+			self.map.get("hgt")?
 		};
-		let index = raw.len() - 2;
+		let index = raw.len().checked_sub(2)?;
+		if !raw.is_char_boundary(index) { return None; }
 		let value = {
 			match raw[..index].parse::<i32>() {
 				Ok(number) => number,
@@ -52,8 +54,8 @@ impl Passport<'_> {
 		};
 		let suffix = {
 			match &raw[index..] {
-				"in" if value >= 59 && value <= 76 => "in",
-				"cm" if value >= 150 && value <= 193 => "cm",
+				"in" if (59..=76).contains(&value) => "in",
+				"cm" if (150..=193).contains(&value) => "cm",
 				_ => return None
 			}
 		};
@@ -63,13 +65,10 @@ impl Passport<'_> {
 
 	fn hcl(&self) -> Option<&str> {
 		let value = {
-			match self.map.get("hcl") {
-				None => return None,
-				Some(line) => line
-			}
+			self.map.get("hcl")?
 		};
 
-		if value.chars().nth(0).unwrap() == '#' && value.len() == 7 {
+		if value.starts_with('#') && value.len() == 7 {
 			return Some(value)
 		}
 
@@ -98,24 +97,27 @@ impl Passport<'_> {
 }
 
 
-fn split_field(data: &str) -> (&str, &str) {
-	let parts: Vec<&str> = data.split(':').collect();
-	return  (parts[0], parts[1])
+fn split_field(data: &str) -> Result<(&str, &str)> {
+	return data.split_once(':').ok_or_else(|| Error::new(format!("field '{}' has no ':'", data)));
 }
 
-fn scan_passport(data: &str) -> HashMap<&str, &str> {
-	let fields: Vec<(&str, &str)> = data.split_whitespace().map(|field| split_field(field)).collect();
+pub fn scan_passport(data: &[String]) -> Result<HashMap<&str, &str>> {
+	let fields: Vec<(&str, &str)> = data.iter().flat_map(|line| line.split_whitespace()).map(split_field).collect::<Result<_>>()?;
 	let mut field_map = HashMap::new();
 
 	for field in fields {
 		field_map.insert(field.0, field.1);
 	}
-	return field_map;
+	return Ok(field_map);
 }
 
-fn check_passport(data: &str) -> bool {
-	let field_map = scan_passport(data);
-	return check_passport_from_hash(&field_map);
+pub fn check_passport(data: &[String]) -> Result<bool> {
+	let field_map = scan_passport(data)?;
+	return Ok(check_passport_from_hash(&field_map));
+}
+
+fn passport_error(index: usize, error: Error) -> Error {
+	return Error::new(format!("passport {}: {}", index + 1, error.message));
 }
 
 fn check_passport_from_hash(data: &HashMap<&str, &str>) -> bool {
@@ -129,10 +131,14 @@ fn check_passport_from_hash(data: &HashMap<&str, &str>) -> bool {
 	return true
 }
 
-pub fn part1(data: &String) -> i32 {
-	let potential_passports: Vec<&str> = data.split("\r\n\r\n").collect();
+pub fn part1(data: &[Vec<String>]) -> Result<i32> {
+	let mut count = 0;
+
+	for (index, potential) in data.iter().enumerate() {
+		if check_passport(potential).map_err(|e| passport_error(index, e))? { count += 1; }
+	}
 
-	return potential_passports.iter().filter(|potential| check_passport(potential)).count() as i32;
+	return Ok(count);
 }
 
 fn validate_passport(passport: &Passport) -> bool {
@@ -147,16 +153,17 @@ fn validate_passport(passport: &Passport) -> bool {
 	return true;
 }
 
-pub fn part2(data: &String) -> i32 {
-	let potential_passports = data.split("\r\n\r\n").map(|data| scan_passport(&data));
-	let passports: Vec<Passport> = potential_passports.map(|p| Passport { map: p }).collect();
+pub fn part2(data: &[Vec<String>]) -> Result<i32> {
+	let potential_passports = data.iter().enumerate().map(|(index, data)| scan_passport(data).map_err(|e| passport_error(index, e)));
+	let passports: Vec<Passport> = potential_passports.map(|p| p.map(|map| Passport { map })).collect::<Result<_>>()?;
 
-	return passports.iter().filter(|passport| validate_passport(passport)).count() as i32;
+	return Ok(passports.iter().filter(|passport| validate_passport(passport)).count() as i32);
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::input::read_records;
 
 	#[test]
 	fn test_parsing() {
@@ -174,9 +181,7 @@ hgt:179cm
 hcl:#cfa07d eyr:2025 pid:166559648
 iyr:2011 ecl:brn hgt:59in";
 
-		let potential_passports: Vec<&str> = data.split("\n\n").collect();
-		let count = potential_passports.iter().filter(|potential| check_passport(potential)).count();
-
-		assert_eq!(count, 2);
+		assert_eq!(part1(&read_records(data)).unwrap(), 2);
+		assert_eq!(part1(&read_records(&data.replace('\n', "\r\n"))).unwrap(), 2);
 	}
 }
\ No newline at end of file
@@ -1,17 +1,21 @@
-pub fn part1(data: &Vec<String>) -> i32 {
-	return *get_all_ids(data).iter().max().unwrap();
+use crate::error::{self, Error, Result};
+
+pub const VERSION: &str = "2";
+
+pub fn part1(data: &[String]) -> Result<i32> {
+	return get_all_ids(data)?.into_iter().max().ok_or_else(|| Error::new("no boarding passes"));
 }
 
-fn get_all_ids(data: &Vec<String>) -> Vec<i32> {
-	let seats = Seat::build_seats(&data);
-	return seats.iter().map(|seat| seat.id).collect();
+fn get_all_ids(data: &[String]) -> Result<Vec<i32>> {
+	let seats = Seat::build_seats(data)?;
+	return Ok(seats.iter().map(|seat| seat.id).collect());
 }
 
-pub fn part2(data: &Vec<String>) -> i32 {
-	let seats = Seat::build_seats(&data);
-	let seat_ids = get_all_ids(data);
+pub fn part2(data: &[String]) -> Result<i32> {
+	let seats = Seat::build_seats(data)?;
+	let seat_ids = get_all_ids(data)?;
 
-	let mut plane = Plane::new(data.len());
+	let mut plane = Plane::new(ROWS);
 
 	for seat in seats {
 		let row = seat.row;
@@ -27,19 +31,21 @@ pub fn part2(data: &Vec<String>) -> i32 {
 		let minus_one = seat_ids.iter().find(|&&x| x == id - 1);
 
 		if plus_one.is_some() && minus_one.is_some() {
-			return id;
+			return Ok(id);
 		}
 	}
 
-	return -1;
+	return Err(Error::new("no empty seat has occupied seats on both sides"));
 }
 
+const ROWS: usize = 128;
+
 
 #[derive(Clone)]
-struct Seat {
-	id: i32,
-	row: usize,
-	col: usize
+pub struct Seat {
+	pub id: i32,
+	pub row: usize,
+	pub col: usize
 }
 
 #[derive(Clone)]
@@ -54,18 +60,12 @@ impl Row {
 		return row;
 	}
 
-	fn find_empty_seat(&self) -> Option<Seat> {
+	// Every gap in the row counts; the first one isn't always the seat with neighbours on both sides
+	fn find_empty_seats(&self) -> Vec<Seat> {
 		let cols: Vec<usize> = self.seats.iter().map(|seat| seat.col).collect();
 		let row = self.seats[0].row;
 
-		for col in 0..7 {
-			match cols.iter().find(|&&x| x == col) {
-				Some(_) => continue,
-				None => return Some(Seat { row: row, col: col, id: seat_id(row as i32, col as i32) })
-			}
-		}
-
-		return None;
+		return (0..8).filter(|col| !cols.contains(col)).map(|col| Seat { row, col, id: seat_id(row as i32, col as i32) }).collect();
 	}
 }
 
@@ -85,12 +85,9 @@ impl Plane {
 		let mut empty_seats = Vec::<Seat>::new();
 
 		for row in &self.rows {
-			if row.seats.len() == 0 || row.seats.len() == 8 { continue; }
+			if row.seats.is_empty() || row.seats.len() == 8 { continue; }
 
-			match row.find_empty_seat() {
-				Some(s) => empty_seats.push(s),
-				_ => continue
-			}
+			empty_seats.extend(row.find_empty_seats());
 		}
 
 		return empty_seats;
@@ -98,30 +95,21 @@ impl Plane {
 }
 
 impl Seat {
-	fn build_seats(data: &Vec<String>) -> Vec<Seat> {
-		return data.iter().map(|line| Seat::parse(line.as_bytes()).unwrap()).collect();
+	pub fn build_seats(data: &[String]) -> Result<Vec<Seat>> {
+		return error::parse_lines(data, |line| Seat::parse(line.as_bytes()));
 	}
 
-	fn parse(code: &[u8]) -> Option<Seat> {
-		let row = {
-			match decode_row(&code) {
-				Ok(value) => value,
-				_ => return None
-			}
-		};
+	pub fn parse(code: &[u8]) -> Result<Seat> {
+		if code.len() != 10 { return Err(Error::new(format!("boarding pass is {} characters long, expected 10", code.len()))); }
 
-		let col = {
-			match decode_col(&code) {
-				Ok(value) => value,
-				_ => return None
-			}
-		};
+		let row = decode_row(code)?;
+		let col = decode_col(code)?;
 
-		return Some(Seat { id: seat_id(row, col), row: row as usize, col: col as usize});
+		return Ok(Seat { id: seat_id(row, col), row: row as usize, col: col as usize});
 	}
 }
 
-fn decode_seat(code: &[u8], lower_code: u8, upper_code: u8, max_seat: i32) -> Result<i32, &str> {
+fn decode_seat(code: &[u8], lower_code: u8, upper_code: u8, max_seat: i32) -> Result<i32> {
 	let mut seat_min = 0;
 	let mut seat_max = max_seat;
 	
@@ -130,7 +118,7 @@ fn decode_seat(code: &[u8], lower_code: u8, upper_code: u8, max_seat: i32) -> Re
 			match dir {
 				x if *x == lower_code => return Ok(seat_min),
 				x if *x == upper_code => return Ok(seat_max),
-				_ => return Err("unexpected character")
+				x => return Err(unexpected_character(*x))
 			}
 		}
 
@@ -138,26 +126,30 @@ fn decode_seat(code: &[u8], lower_code: u8, upper_code: u8, max_seat: i32) -> Re
 			match dir {
 				x if *x == lower_code => seat_max = (seat_min + seat_max) / 2,
 				x if *x == upper_code => seat_min = (seat_min + seat_max + 1) / 2,
-				_ => return Err("unexpected character")
+				x => return Err(unexpected_character(*x))
 			}
 		}
 	}
 
-	if seat_min != seat_max { return Err("didn't narrow down to one seat"); }
+	if seat_min != seat_max { return Err(Error::new("didn't narrow down to one seat")); }
 
 	return Ok(seat_max);
 }
 
-fn seat_id(row: i32, col: i32) -> i32 {
+fn unexpected_character(c: u8) -> Error {
+	return Error::new(format!("unexpected character '{}'", c as char));
+}
+
+pub fn seat_id(row: i32, col: i32) -> i32 {
 	return row * 8 + col;
 }
 
-fn decode_row(code: &[u8]) -> Result<i32, &str> {
-	return decode_seat(&code, 'F' as u8, 'B' as u8, 127);
+pub fn decode_row(code: &[u8]) -> Result<i32> {
+	return decode_seat(code, b'F', b'B', 127);
 }
 
-fn decode_col(code: &[u8]) -> Result<i32, &str> {
-	return decode_seat(&code[7..], 'L' as u8, 'R' as u8, 7);
+pub fn decode_col(code: &[u8]) -> Result<i32> {
+	return decode_seat(&code[7..], b'L', b'R', 7);
 }
 
 #[cfg(test)]
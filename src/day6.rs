@@ -1,14 +1,18 @@
 use std::collections::HashSet;
 
-pub fn part1(data: &Vec<String>) -> usize {
-	return process_data(data);
+use crate::error::Result;
+
+pub const VERSION: &str = "1";
+
+pub fn part1(data: &[Vec<String>]) -> Result<usize> {
+	return Ok(process_data(data));
 }
 
-pub fn part2(data: &Vec<String>) -> usize {
-	return process_all_data(data);
+pub fn part2(data: &[Vec<String>]) -> Result<usize> {
+	return Ok(process_all_data(data));
 }
 
-fn parse_group_questions(data: &Vec<&str>) -> HashSet<char> {
+fn parse_group_questions(data: &[String]) -> HashSet<char> {
 	let mut set = HashSet::<char>::new();
 	for line in data.iter() {
 		for c in line.chars() {
@@ -19,10 +23,15 @@ fn parse_group_questions(data: &Vec<&str>) -> HashSet<char> {
 	return set;
 }
 
-fn parse_all_group_questions(data: &Vec<&str>) -> HashSet<char> {
+fn parse_all_group_questions(data: &[String]) -> HashSet<char> {
 	let mut set = HashSet::<char>::new();
 
-	for c in data.first().unwrap().chars() {
+	let first = match data.first() {
+		Some(line) => line,
+		None => return set
+	};
+
+	for c in first.chars() {
 		set.insert(c);
 	}
 
@@ -42,65 +51,22 @@ fn parse_all_group_questions(data: &Vec<&str>) -> HashSet<char> {
 	return set;
 }
 
-struct DataProcessor<'a> {
-	lines: Vec<&'a str>,
-}
-
-impl<'a> DataProcessor<'a> {
-	fn add(& mut self, line: &'a str) {
-		self.lines.push(line);
-	}
-
-	fn process(& mut self) -> HashSet<char> {
-		let set = parse_group_questions(&self.lines);
-		self.lines.clear();
-		return set;
-	}
-
-	fn process_all(& mut self) -> HashSet<char> {
-		let set = parse_all_group_questions(&self.lines);
-		self.lines.clear();
-		return set;
-	}
+pub fn process_data(data: &[Vec<String>]) -> usize {
+	return data.iter().map(|group| parse_group_questions(group).len()).sum();
 }
 
-fn process_data(data: &Vec<String>) -> usize {
-	let mut total = 0;
-	let mut processor = DataProcessor { lines: Vec::new() };
-
-	for line in data {
-		match line.as_str() {
-			"" => total += processor.process().len(),
-			x => processor.add(x)
-		}	
-	}
-	total += processor.process().len();
-
-	return total;
-}
-
-fn process_all_data(data: &Vec<String>) -> usize {
-	let mut total = 0;
-	let mut processor = DataProcessor { lines: Vec::new() };
-
-	for line in data {
-		match line.as_str() {
-			"" => total += processor.process_all().len(),
-			x => processor.add(x)
-		}
-	}
-	total += processor.process_all().len();
-
-	return total;
+pub fn process_all_data(data: &[Vec<String>]) -> usize {
+	return data.iter().map(|group| parse_all_group_questions(group).len()).sum();
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::input::read_records;
 
 	#[test]
 	fn process_works() {
-		let data: Vec<String> = "abc
+		let data = read_records("abc
 
 a
 b
@@ -115,14 +81,14 @@ a
 a
 
 b
-".lines().map(|line| String::from(line)).collect();
+");
 
 		assert_eq!(process_data(&data), 11);
 	}
 
 	#[test]
 	fn process_all_works() {
-		let data: Vec<String> = "abc
+		let data = read_records("abc
 
 a
 b
@@ -137,7 +103,7 @@ a
 a
 
 b
-".lines().map(|line| String::from(line)).collect();
+");
 
 		assert_eq!(process_all_data(&data), 6);
 	}
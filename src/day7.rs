@@ -1,82 +1,113 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-pub fn part1(data: &Vec<String>) -> i32 {
-	let rules: Vec<Rule> = data.iter().map(|line| parse_line(line)).collect();
+use crate::checked;
+use crate::error::{self, Error, Result};
+
+pub const VERSION: &str = "2";
+
+pub fn part1(data: &[String]) -> Result<i32> {
+	let rules: Vec<Rule> = error::parse_lines(data, parse_line)?;
 	let bag_map = build_rulemap(&rules);
 
-	return rules.iter().filter(|rule| can_contain_gold(rule, &bag_map)).count() as i32;
+	let mut count = 0;
+	for rule in rules.iter() {
+		if can_contain_gold(rule, &bag_map)? { count += 1; }
+	}
+
+	return Ok(count);
 }
 
-pub fn part2(data: &Vec<String>) -> i32 {
-	let rules: Vec<Rule> = data.iter().map(|line| parse_line(line)).collect();
+pub fn part2(data: &[String]) -> Result<i32> {
+	let rules: Vec<Rule> = error::parse_lines(data, parse_line)?;
 	let bag_map = build_rulemap(&rules);
 
-	let gold_bag = bag_map.get("shiny gold").unwrap();
+	let gold_bag = bag_map.get("shiny gold").ok_or_else(|| Error::new("there's no rule for shiny gold bags"))?;
 
 	// Have to remove the shiny gold bag from the count
-	return count_bags(&gold_bag, &bag_map) - 1;
+	return Ok(count_bags(gold_bag, &bag_map)? - 1);
 }
 
-struct Rule {
-	bag_name: String,
-	contains: HashMap<String, i32>
+pub struct Rule {
+	pub bag_name: String,
+	pub contains: HashMap<String, i32>
 }
 
-fn can_contain_gold(bag: &Rule, bags: &HashMap<&str, &Rule>) -> bool {
-	if bag.contains.contains_key("shiny gold") { return true; }
+// A bag that ends up inside itself would send the search round forever, so the bags being looked into are kept
+// in `opened` and meeting one of them again is an error
+fn cycle(bag: &Rule) -> Error {
+	return Error::new(format!("{} bags end up containing themselves", bag.bag_name));
+}
 
-	for sub_bag in bag.contains.iter() {
-		let bagstr = sub_bag.0.as_str();
-		if bags.contains_key(bagstr) {
-			if can_contain_gold(bags.get(sub_bag.0.as_str()).unwrap(), bags) { return true; }
+pub fn can_contain_gold<'a>(bag: &'a Rule, bags: &HashMap<&str, &'a Rule>) -> Result<bool> {
+	return search_gold(bag, bags, &mut HashSet::new());
+}
+
+fn search_gold<'a>(bag: &'a Rule, bags: &HashMap<&str, &'a Rule>, opened: &mut HashSet<&'a str>) -> Result<bool> {
+	if bag.contains.contains_key("shiny gold") { return Ok(true); }
+	if !opened.insert(&bag.bag_name) { return Err(cycle(bag)); }
+
+	let mut found = false;
+	for sub_bag in bag.contains.keys() {
+		if let Some(rule) = bags.get(sub_bag.as_str()) {
+			if search_gold(rule, bags, opened)? { found = true; break; }
 		}
 	}
 
-	return false;
+	opened.remove(bag.bag_name.as_str());
+	return Ok(found);
 }
 
-fn count_bags(bag: &Rule, bags: &HashMap<&str, &Rule>) -> i32 {
+pub fn count_bags<'a>(bag: &'a Rule, bags: &HashMap<&str, &'a Rule>) -> Result<i32> {
+	return count_inside(bag, bags, &mut HashSet::new());
+}
+
+fn count_inside<'a>(bag: &'a Rule, bags: &HashMap<&str, &'a Rule>, opened: &mut HashSet<&'a str>) -> Result<i32> {
 	// The bag itself counts as 1, so an empty bag is one bag
-	if bag.contains.is_empty() { return 1; }
+	if bag.contains.is_empty() { return Ok(1); }
+	if !opened.insert(&bag.bag_name) { return Err(cycle(bag)); }
 
 	let mut total = 1;
 	for sub_bag in bag.contains.iter() {
-		total += sub_bag.1 * count_bags(bags.get(sub_bag.0.as_str()).unwrap(), bags);
+		let rule = bags.get(sub_bag.0.as_str()).ok_or_else(|| Error::new(format!("there's no rule for {} bags", sub_bag.0)))?;
+		let inside = checked::mul(*sub_bag.1, count_inside(rule, bags, opened)?, "the number of bags")?;
+		total = checked::add(total, inside, "the number of bags")?;
 	}
 
-	return total;
+	opened.remove(bag.bag_name.as_str());
+	return Ok(total);
 }
 
-fn map_contains(line: &str) -> HashMap<String, i32> {
+fn map_contains(line: &str) -> Result<HashMap<String, i32>> {
 	let mut map = HashMap::<String, i32>::new();
 	// contains "no other bags", return empty map
-	if line.starts_with("no") { return map; }
+	if line.starts_with("no") { return Ok(map); }
 
 	for bag in line.split(',') {
 		let parts: Vec<&str> = bag.split_whitespace().collect();
-		let name = String::from(parts[1]) + " " + parts[2];
+		if parts.len() < 3 { return Err(Error::new(format!("'{}' should be a count and a bag colour", bag.trim()))); }
 
-		let count = parts[0].parse::<i32>().unwrap();
+		let name = String::from(parts[1]) + " " + parts[2];
+		let count = error::parse_number::<i32>(parts[0])?;
 
 		map.insert(name, count);
 	}
 
-	return map;
+	return Ok(map);
 }
 
-fn parse_line(line: &str) -> Rule {
-	let parts: Vec<&str> = line.split(" contain ").collect();
-	let rules = map_contains(&parts[1]);
-	let name = parts[0].strip_suffix(" bags").unwrap();
+pub fn parse_line(line: &str) -> Result<Rule> {
+	let (bag, contents) = line.split_once(" contain ").ok_or_else(|| Error::new("rule is missing ' contain '"))?;
+	let rules = map_contains(contents)?;
+	let name = bag.strip_suffix(" bags").ok_or_else(|| Error::new(format!("'{}' should end in ' bags'", bag)))?;
 
-	return Rule { bag_name: String::from(name), contains: rules };
+	return Ok(Rule { bag_name: String::from(name), contains: rules });
 }
 
-fn build_rulemap(rules: &Vec<Rule>) -> HashMap<&str, &Rule> {
+pub fn build_rulemap(rules: &[Rule]) -> HashMap<&str, &Rule> {
 	let mut map = HashMap::<&str, &Rule>::new();
 
 	for rule in rules.iter() {
-		map.insert(&rule.bag_name, &rule);
+		map.insert(&rule.bag_name, rule);
 	}
 
 	return map;
@@ -96,9 +127,9 @@ shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
 dark olive bags contain 3 faded blue bags, 4 dotted black bags.
 vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
 faded blue bags contain no other bags.
-dotted black bags contain no other bags.".lines().map(|line| String::from(line)).collect();
+dotted black bags contain no other bags.".lines().map(String::from).collect();
 
-		assert_eq!(part1(&lines), 4);
+		assert_eq!(part1(&lines).unwrap(), 4);
 	}
 
 	#[test]
@@ -110,8 +141,19 @@ dark orange bags contain 2 dark yellow bags.
 dark yellow bags contain 2 dark green bags.
 dark green bags contain 2 dark blue bags.
 dark blue bags contain 2 dark violet bags.
-dark violet bags contain no other bags.".lines().map(|line| String::from(line)).collect();
+dark violet bags contain no other bags.".lines().map(String::from).collect();
 
-		assert_eq!(part2(&lines), 126);
+		assert_eq!(part2(&lines).unwrap(), 126);
+	}
+
+	#[test]
+	fn bags_inside_themselves_are_reported() {
+		let lines: Vec<String> = "light red bags contain 1 light red bag.".lines().map(String::from).collect();
+		assert_eq!(part1(&lines).unwrap_err(), Error::new("light red bags end up containing themselves"));
+
+		let lines: Vec<String> = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 1 dark blue bag.
+dark blue bags contain 3 shiny gold bags.".lines().map(String::from).collect();
+		assert_eq!(part2(&lines).unwrap_err(), Error::new("shiny gold bags end up containing themselves"));
 	}
 }
\ No newline at end of file
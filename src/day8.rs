@@ -1,19 +1,25 @@
-pub fn part1(data: &Vec<String>) -> i64 {
-	let mut console = Console::new(data);
-	return console.run().0;
+use crate::cancel;
+use crate::checked;
+use crate::error::{self, Error, Result};
+
+pub const VERSION: &str = "2";
+
+pub fn part1(data: &[String]) -> Result<i64> {
+	let mut console = Console::new(data)?;
+	return Ok(console.run()?.0);
 }
 
-pub fn part2(data: &Vec<String>) -> i64 {
-	let mut console = Console::new(data);
+pub fn part2(data: &[String]) -> Result<i64> {
+	let mut console = Console::new(data)?;
 
 	for index in 0..console.program.len() {
 		if console.program[index].operation != Operations::Acc {
-			let (value, reason) = console.swap_and_run(index);
-			if reason == TerminationReason::Completed { return value; }
+			let (value, reason) = console.swap_and_run(index)?;
+			if reason == TerminationReason::Completed { return Ok(value); }
 		}
 	}
 	
-	return 0;
+	return Err(Error::new("no single nop/jmp swap lets the program finish"));
 }
 
 fn swap_operation(instruction: &mut Instruction) -> bool {
@@ -24,29 +30,29 @@ fn swap_operation(instruction: &mut Instruction) -> bool {
 	}
 }
 
-fn parse_line(line: &str) -> Instruction {
+pub fn parse_line(line: &str) -> Result<Instruction> {
 	let parts: Vec<&str> = line.split_whitespace().collect();
+	if parts.len() != 2 { return Err(Error::new(format!("expected an operation and an argument, found '{}'", line))); }
 
 	let operation = {
 		match parts[0] {
 			"nop" => Operations::Nop,
 			"acc" => Operations::Acc,
 			"jmp" => Operations::Jmp,
-			_ => panic!("bad operation found?")
+			x => return Err(Error::new(format!("unknown operation '{}'", x)))
 		}
 	};
 
-	let argument = parts[1].parse::<i64>().unwrap();
+	let argument = error::parse_number::<i64>(parts[1])?;
 
-	return Instruction { operation: operation, value: argument, run_before: false };
+	return Ok(Instruction { operation, value: argument, run_before: false });
 }
 
-fn parse_data(data: &Vec<String>) -> Vec<Instruction> {
-	let instructions: Vec<Instruction> = data.iter().map(|line| parse_line(line)).collect();
-	return instructions;
+pub fn parse_data(data: &[String]) -> Result<Vec<Instruction>> {
+	return error::parse_lines(data, parse_line);
 }
 
-struct Console {
+pub struct Console {
 	accumulator: i64,
 	pc: i64,
 	program: Vec<Instruction>
@@ -54,12 +60,12 @@ struct Console {
 }
 
 impl Console {
-	fn new(data: &Vec<String>) -> Console {
-		let console = Console { accumulator: 0, pc: 0, program: parse_data(data) };
-		return console;
+	pub fn new(data: &[String]) -> Result<Console> {
+		let console = Console { accumulator: 0, pc: 0, program: parse_data(data)? };
+		return Ok(console);
 	}
 
-	fn reset(&mut self) {
+	pub fn reset(&mut self) {
 		self.pc = 0;
 		self.accumulator = 0;
 		for instruction in &mut self.program {
@@ -67,7 +73,7 @@ impl Console {
 		}
 	}
 
-	fn swap_and_run(&mut self, index_to_swap: usize) -> (i64, TerminationReason) {
+	pub fn swap_and_run(&mut self, index_to_swap: usize) -> Result<(i64, TerminationReason)> {
 		swap_operation(&mut self.program[index_to_swap]);
 		let result = self.run();
 		self.reset();
@@ -76,47 +82,50 @@ impl Console {
 		return result;
 	}
 
-	fn run(&mut self) -> (i64, TerminationReason) {
-		while self.step().is_none() {}
-
-		return (self.accumulator, self.step().unwrap());
+	pub fn run(&mut self) -> Result<(i64, TerminationReason)> {
+		loop {
+			if let Some(reason) = self.step()? { return Ok((self.accumulator, reason)); }
+			cancel::check()?;
+		}
 	}
 
-	fn step(&mut self) -> Option<TerminationReason> {
-		if self.pc as usize >= self.program.len() { return Some(TerminationReason::Completed); }
+	pub fn step(&mut self) -> Result<Option<TerminationReason>> {
+		if self.pc as usize >= self.program.len() { return Ok(Some(TerminationReason::Completed)); }
 
 		let instruction = &mut self.program[self.pc as usize];
-		if instruction.run_before { return Some(TerminationReason::LoopDetected); }
-
-		match instruction.operation {
-			Operations::Acc => self.accumulator += instruction.value,
-			Operations::Jmp => self.pc += instruction.value - 1,
-			_ => ()
-
-		}
-		self.pc += 1;
+		if instruction.run_before { return Ok(Some(TerminationReason::LoopDetected)); }
+
+		let offset = match instruction.operation {
+			Operations::Acc => {
+				self.accumulator = checked::add(self.accumulator, instruction.value, "the accumulator")?;
+				1
+			},
+			Operations::Jmp => instruction.value,
+			_ => 1
+		};
 		instruction.run_before = true;
-		return None;
+		self.pc = checked::add(self.pc, offset, "the program counter")?;
+		return Ok(None);
 	}
 }
 
 #[derive(PartialEq, Eq)]
-enum Operations {
+pub enum Operations {
 	Acc,
 	Jmp,
 	Nop
 }
 
 #[derive(PartialEq, Eq)]
-enum TerminationReason {
+pub enum TerminationReason {
 	Completed,
 	LoopDetected
 }
 
-struct Instruction {
-	operation: Operations,
-	value: i64,
-	run_before: bool
+pub struct Instruction {
+	pub operation: Operations,
+	pub value: i64,
+	pub run_before: bool
 }
 
 #[cfg(test)]
@@ -133,9 +142,9 @@ jmp -3
 acc -99
 acc +1
 jmp -4
-acc +6".lines().map(|line| String::from(line)).collect();
+acc +6".lines().map(String::from).collect();
 
-		assert_eq!(part1(&data), 5);
+		assert_eq!(part1(&data).unwrap(), 5);
 	}
 
 	#[test]
@@ -148,8 +157,32 @@ jmp -3
 acc -99
 acc +1
 jmp -4
-acc +6".lines().map(|line| String::from(line)).collect();
+acc +6".lines().map(String::from).collect();
 		
-		assert_eq!(part2(&data), 8);
+		assert_eq!(part2(&data).unwrap(), 8);
+	}
+
+	#[test]
+	fn bad_programs_are_reported() {
+		let data: Vec<String> = "nop +0
+acc +1
+hcf +4".lines().map(String::from).collect();
+
+		assert_eq!(part1(&data).unwrap_err(), Error::at_line(3, "unknown operation 'hcf'"));
+	}
+
+	#[test]
+	fn accumulator_overflow_is_reported() {
+		let data: Vec<String> = vec![format!("acc +{}", i64::MAX), String::from("acc +1")];
+		assert_eq!(part1(&data).unwrap_err(), Error::new("the accumulator overflows i64"));
+	}
+
+	#[test]
+	fn jumps_out_of_range_are_reported() {
+		let data: Vec<String> = vec![String::from("nop +0"), format!("jmp +{}", i64::MAX)];
+		assert_eq!(part1(&data).unwrap_err(), Error::new("the program counter overflows i64"));
+
+		let data: Vec<String> = vec![format!("jmp {}", i64::MIN)];
+		assert!(part1(&data).is_ok());
 	}
 }
\ No newline at end of file
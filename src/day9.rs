@@ -1,54 +1,69 @@
 use std::collections::VecDeque;
 
-pub fn part1(data: &Vec<String>) -> i64 {
+use crate::checked;
+use crate::error::{self, Error, Result};
+
+pub const VERSION: &str = "2";
+
+pub fn part1(data: &[String]) -> Result<i64> {
 	let mut breaker = Breaker::new(25);
 
-	for number in data.iter().map(|line| line.parse::<i64>().unwrap()) {
-		if !breaker.process(number) { return number; }
+	for number in error::parse_lines(data, error::parse_number::<i64>)? {
+		if !breaker.process(number) { return Ok(number); }
 	}
-	// yes this should probably actually return Result or Option but whatever
-	return -1;
+
+	return Err(Error::new("every number is the sum of two of the previous 25"));
 }
 
-pub fn part2(data: &Vec<String>) -> i64 {
-	let target = part1(data);
-	let numbers: Vec<i64> = data.iter().map(|line| line.parse::<i64>().unwrap()).collect();
+pub fn part2(data: &[String]) -> Result<i64> {
+	let target = part1(data)?;
+	let numbers: Vec<i64> = error::parse_lines(data, error::parse_number::<i64>)?;
+
+	// A run is abandoned as soon as it passes the target, which is only right if adding a number can't shrink it
+	if let Some(index) = numbers.iter().position(|number| *number < 0) {
+		return Err(Error::at_line(index + 1, format!("{} is negative, contiguous runs need numbers of 0 or more", numbers[index])));
+	}
 
 	for i in 0..numbers.len() {
 		let mut sum = numbers[i];
 		for j in (i + 1)..numbers.len() {
-			sum += numbers[j];
+			// A run too big to add up has overshot the target like any other, and can't be the answer either
+			sum = match sum.checked_add(numbers[j]) {
+				Some(sum) => sum,
+				None => break
+			};
 
-			if sum > target { continue; }
+			if sum > target { break; }
 			if sum == target { return weakness_sum(&numbers[i..=j]); }
 		}
 	}
 
-	return -1;
+	return Err(Error::new(format!("no contiguous run sums to {}", target)));
 }
 
-fn weakness_sum(numbers: &[i64]) -> i64 {
+pub fn weakness_sum(numbers: &[i64]) -> Result<i64> {
 	let biggest = numbers.iter().max().unwrap();
 	let smallest = numbers.iter().min().unwrap();
 
-	return biggest + smallest;
+	return checked::add(*biggest, *smallest, "the encryption weakness");
 }
 
-struct Breaker {
+pub struct Breaker {
 	history: VecDeque<i64>,
 	preamble_length: usize
 }
 
 impl Breaker {
-	fn new(preamble_length: usize) -> Breaker {
-		Breaker { history: VecDeque::<i64>::new(), preamble_length: preamble_length }
+	pub fn new(preamble_length: usize) -> Breaker {
+		Breaker { history: VecDeque::<i64>::new(), preamble_length }
 	}
 
 	fn history_pairs(&self) -> Vec<i64> {
 		let mut sums = vec!();
 		for i in 0..self.history.len() {
 			for j in (i + 1)..self.history.len() {
-				sums.push(self.history[i] + self.history[j]);
+				// A pair too big to add up can't match any number that fits in an i64 anyway
+				sums.extend(self.history[i].checked_add(self.history[j]));
 			}	
 		}
 
@@ -57,7 +72,7 @@ impl Breaker {
 
 	// Returns false if the number isn't valid
 	// Returns true if the number is valid or part of the preamble
-	fn process(&mut self, number: i64) -> bool {
+	pub fn process(&mut self, number: i64) -> bool {
 		if self.history.len() < self.preamble_length {
 			self.history.push_back(number);
 			return true;
@@ -116,4 +131,20 @@ mod test {
 
 		assert_eq!(result, 127);
 	}
+
+	#[test]
+	fn runs_past_the_target_are_abandoned() {
+		let mut data: Vec<String> = (1..=25).map(|number: i64| number.to_string()).collect();
+		data.extend(vec![String::from("100"), i64::MAX.to_string(), i64::MAX.to_string()]);
+
+		assert_eq!(part2(&data).unwrap(), 25);
+	}
+
+	#[test]
+	fn negative_numbers_are_refused() {
+		let mut data: Vec<String> = (1..=25).map(|number: i64| number.to_string()).collect();
+		data.extend(vec![String::from("-3"), String::from("100")]);
+
+		assert_eq!(part2(&data).unwrap_err(), Error::at_line(26, "-3 is negative, contiguous runs need numbers of 0 or more"));
+	}
 }
\ No newline at end of file
@@ -0,0 +1,77 @@
+use std::{fmt, str::FromStr};
+
+// Line numbers are 1-based so they match what an editor shows for the input file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+	pub day: Option<i32>,
+	pub line: Option<usize>,
+	pub message: String
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+	pub fn new(message: impl Into<String>) -> Error {
+		return Error { day: None, line: None, message: message.into() };
+	}
+
+	pub fn at_line(line: usize, message: impl Into<String>) -> Error {
+		return Error { day: None, line: Some(line), message: message.into() };
+	}
+
+	// The innermost context wins, so a parser that already knows its line number keeps it
+	pub fn with_line(self, line: usize) -> Error {
+		return Error { line: self.line.or(Some(line)), ..self };
+	}
+
+	pub fn with_day(self, day: i32) -> Error {
+		return Error { day: self.day.or(Some(day)), ..self };
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match (self.day, self.line) {
+			(Some(day), Some(line)) => write!(f, "day {}, line {}: {}", day, line, self.message),
+			(Some(day), None) => write!(f, "day {}: {}", day, self.message),
+			(None, Some(line)) => write!(f, "line {}: {}", line, self.message),
+			(None, None) => write!(f, "{}", self.message)
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+pub fn parse_lines<'a, T, F>(data: &'a [String], mut parser: F) -> Result<Vec<T>> where F: FnMut(&'a str) -> Result<T> {
+	return data.iter().enumerate().map(|(index, line)| parser(line).map_err(|e| e.with_line(index + 1))).collect();
+}
+
+pub fn parse_number<T: FromStr>(value: &str) -> Result<T> {
+	return value.trim().parse::<T>().map_err(|_| Error::new(format!("'{}' is not a valid number", value)));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn display_works() {
+		assert_eq!(Error::new("oops").to_string(), "oops");
+		assert_eq!(Error::at_line(3, "oops").to_string(), "line 3: oops");
+		assert_eq!(Error::at_line(3, "oops").with_day(8).to_string(), "day 8, line 3: oops");
+		assert_eq!(Error::new("oops").with_day(8).to_string(), "day 8: oops");
+	}
+
+	#[test]
+	fn inner_context_wins() {
+		assert_eq!(Error::at_line(3, "oops").with_line(7).line, Some(3));
+		assert_eq!(Error::new("oops").with_day(2).with_day(5).day, Some(2));
+	}
+
+	#[test]
+	fn parse_lines_reports_the_line() {
+		let data: Vec<String> = vec![String::from("1"), String::from("2"), String::from("x")];
+		assert_eq!(parse_lines(&data[..2], parse_number::<i32>).unwrap(), vec![1, 2]);
+		assert_eq!(parse_lines(&data, parse_number::<i32>).unwrap_err(), Error::at_line(3, "'x' is not a valid number"));
+	}
+}
\ No newline at end of file
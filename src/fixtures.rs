@@ -0,0 +1,130 @@
+use std::{
+	fs,
+	path::{Path, PathBuf}
+};
+
+use crate::error::{Error, Result};
+use crate::input::Source;
+use crate::results::{DayResults, PartResult, Status};
+use crate::runner;
+use crate::solver::Solver;
+
+// Examples live in data/examples/dayN/NAME.txt, with the answers they should give in NAME.expected as
+// `partP = ANSWER` lines for whichever parts the example covers
+pub const ROOT: &str = "data/examples";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixture {
+	pub day: i32,
+	pub input: PathBuf,
+	pub expected: Vec<(i32, String)>
+}
+
+impl Fixture {
+	pub fn name(&self) -> String {
+		return self.input.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+	}
+
+	pub fn expected_path(&self) -> PathBuf {
+		return self.input.with_extension("expected");
+	}
+
+	pub fn parts(&self) -> Vec<i32> {
+		return self.expected.iter().map(|(part, _)| *part).collect();
+	}
+
+	// Pairs the results of running the fixture's parts, in the order parts() gave them, with their expected answers
+	pub fn outcomes(&self, results: DayResults) -> Vec<Outcome> {
+		return self.expected.iter().zip(results.parts).map(|((_, expected), result)| Outcome { expected: expected.clone(), result }).collect();
+	}
+}
+
+pub struct Outcome {
+	pub expected: String,
+	pub result: PartResult
+}
+
+impl Outcome {
+	pub fn passed(&self) -> bool {
+		return self.result.status == Status::Solved && self.result.answer == self.expected;
+	}
+}
+
+pub fn parse_expected(text: &str) -> Result<Vec<(i32, String)>> {
+	let mut expected = vec![];
+
+	for (index, line) in text.lines().enumerate() {
+		let line = line.split('#').next().unwrap_or("").trim();
+		if line.is_empty() { continue; }
+
+		let entry = line.split_once('=').and_then(|(key, value)| Some((key.trim().strip_prefix("part")?.parse::<i32>().ok()?, value.trim())));
+		match entry {
+			Some((part, value)) if !value.is_empty() => expected.push((part, String::from(value))),
+			_ => return Err(Error::at_line(index + 1, "expected `partN = answer`"))
+		}
+	}
+
+	if expected.is_empty() { return Err(Error::new("there are no answers")); }
+	return Ok(expected);
+}
+
+fn read_dir(path: &Path) -> Result<Vec<PathBuf>> {
+	let entries = fs::read_dir(path).map_err(|e| Error::new(format!("couldn't read {}: {}", path.display(), e)))?;
+	let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect();
+	paths.sort();
+
+	return Ok(paths);
+}
+
+// A day without an examples directory just has no fixtures
+pub fn for_day(root: &Path, day: i32) -> Result<Vec<Fixture>> {
+	let dir = root.join(format!("day{}", day));
+	if !dir.is_dir() { return Ok(vec![]); }
+
+	let mut fixtures = vec![];
+	for input in read_dir(&dir)?.into_iter().filter(|path| path.extension().is_some_and(|ext| ext == "txt")) {
+		let expected_path = input.with_extension("expected");
+		let text = fs::read_to_string(&expected_path).map_err(|e| Error::new(format!("couldn't read {}: {}", expected_path.display(), e)))?;
+		let expected = parse_expected(&text).map_err(|e| Error::new(format!("{}: {}", expected_path.display(), e)))?;
+		fixtures.push(Fixture { day, input, expected });
+	}
+
+	return Ok(fixtures);
+}
+
+pub fn discover(root: &Path) -> Result<Vec<Fixture>> {
+	let mut fixtures = vec![];
+
+	for dir in read_dir(root)? {
+		let day = dir.file_name().and_then(|name| name.to_str()?.strip_prefix("day")?.parse::<i32>().ok());
+		match day {
+			Some(day) => fixtures.extend(for_day(root, day)?),
+			None => return Err(Error::new(format!("{} isn't a dayN directory", dir.display())))
+		}
+	}
+
+	return Ok(fixtures);
+}
+
+pub fn run(fixture: &Fixture, solver: &dyn Solver) -> Vec<Outcome> {
+	return fixture.outcomes(runner::run_day(solver, &fixture.parts(), &Source::File(fixture.input.clone())));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn expected_answers_parse() {
+		assert_eq!(parse_expected("# comments are fine\npart1 = 5\n\npart2 = abc\n").unwrap(), vec![(1, String::from("5")), (2, String::from("abc"))]);
+		assert_eq!(parse_expected("part one = 5\n").unwrap_err(), Error::at_line(1, "expected `partN = answer`"));
+		assert!(parse_expected("# nothing\n").is_err());
+	}
+
+	#[test]
+	fn fixtures_are_found_per_day() {
+		let fixtures = for_day(Path::new(ROOT), 13).unwrap();
+		assert!(fixtures.iter().any(|fixture| fixture.name() == "example" && fixture.expected[0] == (1, String::from("295"))));
+		assert!(for_day(Path::new(ROOT), 9).unwrap().is_empty());
+	}
+}
\ No newline at end of file
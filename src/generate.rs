@@ -0,0 +1,512 @@
+// Random but valid puzzle inputs, for stress testing the parsers and comparing the solvers against slower
+// reference implementations. The same seed always gives the same input, so a failure can be replayed.
+// Only the days below have a generator so far; the rest come back as an error
+
+use crate::error::{Error, Result};
+
+pub const DAYS: &[i32] = &[2, 5, 7, 8, 14, 16, 18];
+
+// xorshift64*, which is more than random enough for test inputs
+pub struct Rng {
+	state: u64
+}
+
+impl Rng {
+	pub fn new(seed: u64) -> Rng {
+		// A zero state would only ever produce zeroes
+		return Rng { state: (seed ^ 0x9e3779b97f4a7c15).max(1) };
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.state ^= self.state >> 12;
+		self.state ^= self.state << 25;
+		self.state ^= self.state >> 27;
+		return self.state.wrapping_mul(0x2545f4914f6cdd1d);
+	}
+
+	pub fn below(&mut self, bound: u64) -> u64 {
+		return self.next_u64() % bound.max(1);
+	}
+
+	// Both ends are included
+	pub fn range(&mut self, low: i64, high: i64) -> i64 {
+		return low + self.below((high - low + 1) as u64) as i64;
+	}
+
+	pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+		return self.below(denominator) < numerator;
+	}
+
+	pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+		return &items[self.below(items.len() as u64) as usize];
+	}
+
+	pub fn shuffle<T>(&mut self, items: &mut [T]) {
+		for i in (1..items.len()).rev() {
+			items.swap(i, self.below(i as u64 + 1) as usize);
+		}
+	}
+}
+
+// `size` is roughly how many lines, records or instructions the input has
+pub fn generate(day: i32, rng: &mut Rng, size: usize) -> Result<String> {
+	match day {
+		2 => return Ok(day2(rng, size)),
+		5 => return Ok(day5(rng, size)),
+		7 => return Ok(day7(rng, size)),
+		8 => return Ok(day8(rng, size)),
+		14 => return Ok(day14(rng, size)),
+		16 => return Ok(day16(rng, size)),
+		18 => return Ok(day18(rng, size)),
+		_ => return Err(Error::new(format!("no generator for day {}", day)))
+	}
+}
+
+fn lines(lines: Vec<String>) -> String {
+	return lines.join("\n") + "\n";
+}
+
+pub fn day2(rng: &mut Rng, size: usize) -> String {
+	let letters: Vec<char> = "abcde".chars().collect();
+
+	return lines((0..size.max(1)).map(|_| {
+		let length = rng.range(1, 20);
+		let low = rng.range(1, length);
+		let high = rng.range(low, length);
+		let password: String = (0..length).map(|_| *rng.pick(&letters)).collect();
+		format!("{}-{} {}: {}", low, high, rng.pick(&letters), password)
+	}).collect());
+}
+
+pub fn boarding_pass(id: i32) -> String {
+	let row = (0..7).rev().map(|bit| if (id >> 3) & (1 << bit) != 0 { 'B' } else { 'F' });
+	let col = (0..3).rev().map(|bit| if id & (1 << bit) != 0 { 'R' } else { 'L' });
+	return row.chain(col).collect();
+}
+
+// A contiguous run of seats with exactly one missing from the middle, so part 2 always has an answer
+pub fn day5(rng: &mut Rng, size: usize) -> String {
+	let count = size.clamp(3, 1000) as i64;
+	let first = rng.range(1, 1023 - count);
+	let missing = rng.range(first + 1, first + count - 1);
+
+	let mut passes: Vec<String> = (first..=first + count).filter(|id| *id != missing).map(|id| boarding_pass(id as i32)).collect();
+	rng.shuffle(&mut passes);
+	return lines(passes);
+}
+
+const ADJECTIVES: &[&str] = &["light", "dark", "bright", "muted", "shiny", "faded", "dotted", "vibrant", "pale", "clear", "drab", "dull", "mirrored", "plaid", "posh", "striped", "wavy", "dim"];
+const COLORS: &[&str] = &["red", "orange", "white", "yellow", "gold", "olive", "plum", "blue", "black", "green", "tan", "teal", "violet", "crimson", "lime", "maroon", "silver", "beige", "coral", "indigo"];
+
+// Bags only ever contain bags that come later in the list, which keeps the rules acyclic, and shiny gold sits
+// near the end so the number of bags inside it stays small enough to count
+pub fn day7(rng: &mut Rng, size: usize) -> String {
+	let mut names: Vec<String> = ADJECTIVES.iter().flat_map(|adjective| COLORS.iter().map(move |color| format!("{} {}", adjective, color)))
+		.filter(|name| name != "shiny gold").collect();
+	rng.shuffle(&mut names);
+
+	let count = size.clamp(2, names.len());
+	names.truncate(count - 1);
+	names.insert(count - count.min(6), String::from("shiny gold"));
+
+	let mut rules = vec![];
+	for (index, name) in names.iter().enumerate() {
+		let mut later: Vec<&String> = names[index + 1..].iter().collect();
+		rng.shuffle(&mut later);
+		later.truncate(rng.range(0, 3) as usize);
+
+		let contents: Vec<String> = later.iter().map(|inner| match rng.range(1, 4) {
+			1 => format!("1 {} bag", inner),
+			n => format!("{} {} bags", n, inner)
+		}).collect();
+
+		match contents.is_empty() {
+			true => rules.push(format!("{} bags contain no other bags.", name)),
+			false => rules.push(format!("{} bags contain {}.", name, contents.join(", ")))
+		}
+	}
+
+	rng.shuffle(&mut rules);
+	return lines(rules);
+}
+
+fn visited_path(program: &[(&str, i64)]) -> Vec<usize> {
+	let mut pc = 0;
+	let mut visited = vec![];
+	while pc < program.len() && !visited.contains(&pc) {
+		visited.push(pc);
+		match program[pc] {
+			("jmp", offset) => pc = (pc as i64 + offset) as usize,
+			_ => pc += 1
+		}
+	}
+
+	return visited;
+}
+
+// Starts from a program that only jumps forward and so always finishes, then turns one nop it runs into a jmp back
+// to an instruction it ran earlier. The path from there leads straight back to the jmp, so the program loops, and
+// swapping that instruction back is at least one fix for part 2
+pub fn day8(rng: &mut Rng, size: usize) -> String {
+	let length = size.max(3);
+
+	loop {
+		let mut program: Vec<(&str, i64)> = (0..length).map(|index| match rng.range(0, 9) {
+			0..=3 => ("acc", rng.range(-50, 50)),
+			4..=6 if index > 0 => ("nop", -rng.range(1, index as i64)),
+			_ => ("jmp", rng.range(1, 4.min((length - index) as i64)))
+		}).collect();
+
+		let path = visited_path(&program);
+		let candidates: Vec<usize> = path.iter().copied().filter(|index| program[*index].0 == "nop").collect();
+		if candidates.is_empty() { continue; }
+
+		// Forward jumps mean the path is in order, and the first instruction is always on it
+		let index = *rng.pick(&candidates);
+		let earlier: Vec<usize> = path.iter().copied().take_while(|visited| *visited < index).collect();
+		let target = *rng.pick(&earlier);
+		program[index] = ("jmp", target as i64 - index as i64);
+		return lines(program.iter().map(|(operation, argument)| format!("{} {:+}", operation, argument)).collect());
+	}
+}
+
+// Masks have at most four floating bits so part 2 doesn't write millions of addresses
+pub fn day14(rng: &mut Rng, size: usize) -> String {
+	let mut program = vec![];
+
+	while program.len() < size.max(2) {
+		let floating = rng.range(0, 4) as usize;
+		let mut mask: Vec<char> = (0..36).map(|_| if rng.chance(1, 2) { '1' } else { '0' }).collect();
+		for _ in 0..floating {
+			let bit = rng.below(36) as usize;
+			mask[bit] = 'X';
+		}
+		program.push(format!("mask = {}", mask.iter().collect::<String>()));
+
+		for _ in 0..rng.range(1, 4) {
+			program.push(format!("mem[{}] = {}", rng.below(1 << 16), rng.below(1 << 36)));
+		}
+	}
+
+	return lines(program);
+}
+
+const FIELDS: &[&str] = &["departure location", "departure station", "departure platform", "departure track", "departure date", "departure time",
+	"arrival location", "arrival station", "arrival platform", "arrival track", "class", "duration", "price", "route", "row", "seat", "train", "type", "wagon", "zone"];
+
+pub const TICKET_FIELDS: usize = 6;
+
+// Rule r accepts 0 to 10(r + 1) apart from a one value gap, and every column's first valid ticket holds the top of
+// its rule's range, which only that rule and the ones after it accept; that leaves exactly one way to assign fields
+pub fn day16(rng: &mut Rng, size: usize) -> String {
+	let mut names: Vec<&str> = FIELDS.to_vec();
+	rng.shuffle(&mut names);
+	names.truncate(TICKET_FIELDS);
+	if !names.iter().any(|name| name.starts_with("departure")) {
+		names[0] = "departure location";
+	}
+
+	let rule_range = |rule: usize| (5 * (rule as i64 + 1), 10 * (rule as i64 + 1));
+	let rules: Vec<String> = names.iter().enumerate().map(|(rule, name)| {
+		let (gap, top) = rule_range(rule);
+		format!("{}: 0-{} or {}-{}", name, gap, gap + 2, top)
+	}).collect();
+
+	let mut columns: Vec<usize> = (0..TICKET_FIELDS).collect();
+	rng.shuffle(&mut columns);
+
+	let valid_value = |rng: &mut Rng, rule: usize| {
+		let (gap, top) = rule_range(rule);
+		loop {
+			let value = rng.range(1, top);
+			if value != gap + 1 { return value; }
+		}
+	};
+	let ticket = |rng: &mut Rng| columns.iter().map(|rule| valid_value(rng, *rule)).collect::<Vec<i64>>();
+
+	let mine = ticket(rng);
+	let mut nearby = vec![columns.iter().map(|rule| rule_range(*rule).1).collect::<Vec<i64>>()];
+	for _ in 1..size.max(1) {
+		let mut values = ticket(rng);
+		if rng.chance(1, 4) {
+			let column = rng.below(TICKET_FIELDS as u64) as usize;
+			values[column] = 10 * TICKET_FIELDS as i64 + rng.range(1, 100);
+		}
+		nearby.push(values);
+	}
+	rng.shuffle(&mut nearby);
+
+	let join = |values: &[i64]| values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(",");
+	let nearby: Vec<String> = nearby.iter().map(|values| join(values)).collect();
+	return format!("{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}\n", rules.join("\n"), join(&mine), nearby.join("\n"));
+}
+
+fn expression(rng: &mut Rng, nested: bool) -> String {
+	let mut terms = vec![];
+	for _ in 0..rng.range(2, 4) {
+		if !nested && rng.chance(1, 3) {
+			terms.push(format!("({})", expression(rng, true)));
+		} else {
+			terms.push(rng.range(1, 9).to_string());
+		}
+	}
+
+	let mut text = terms[0].clone();
+	for term in &terms[1..] {
+		text += if rng.chance(1, 2) { " + " } else { " * " };
+		text += term;
+	}
+
+	return text;
+}
+
+// Only one level of parentheses, so even an expression that's all multiplication fits in an i64
+pub fn day18(rng: &mut Rng, size: usize) -> String {
+	return lines((0..size.max(1)).map(|_| expression(rng, false)).collect());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::{HashMap, HashSet};
+	use crate::input::{read_lines, read_records};
+	use crate::{day2, day5, day7, day8, day14, day16, day18};
+
+	const SEEDS: u64 = 20;
+
+	#[test]
+	fn rng_is_deterministic() {
+		let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+		assert_eq!((0..10).map(|_| a.next_u64()).collect::<Vec<_>>(), (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+		assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+		assert!((0..1000).map(|_| a.range(-3, 3)).all(|value| (-3..=3).contains(&value)));
+		assert_eq!(generate(8, &mut Rng::new(1), 50), generate(8, &mut Rng::new(1), 50));
+		assert_eq!(generate(1, &mut Rng::new(1), 50).unwrap_err(), Error::new("no generator for day 1"));
+	}
+
+	#[test]
+	fn day2_matches_the_reference() {
+		for seed in 0..SEEDS {
+			let data = read_lines(&day2(&mut Rng::new(seed), 200));
+			let parsed: Vec<(usize, usize, char, Vec<char>)> = data.iter().map(|line| {
+				let fields: Vec<&str> = line.split(['-', ' ', ':']).filter(|field| !field.is_empty()).collect();
+				(fields[0].parse().unwrap(), fields[1].parse().unwrap(), fields[2].chars().next().unwrap(), fields[3].chars().collect())
+			}).collect();
+
+			let first = parsed.iter().filter(|(low, high, c, password)| (*low..=*high).contains(&password.iter().filter(|x| *x == c).count())).count();
+			let second = parsed.iter().filter(|(low, high, c, password)| (password.get(low - 1) == Some(c)) != (password.get(high - 1) == Some(c))).count();
+
+			assert_eq!(day2::part1(&data).unwrap(), first as i32, "seed {}", seed);
+			assert_eq!(day2::part2(&data).unwrap(), second as i32, "seed {}", seed);
+		}
+	}
+
+	#[test]
+	fn day5_matches_the_reference() {
+		for seed in 0..SEEDS {
+			let data = read_lines(&day5(&mut Rng::new(seed), 100 + seed as usize * 40));
+			let mut ids: Vec<i32> = data.iter().map(|pass| i32::from_str_radix(&pass.replace(['F', 'L'], "0").replace(['B', 'R'], "1"), 2).unwrap()).collect();
+			ids.sort_unstable();
+			let missing = ids.windows(2).find(|pair| pair[1] - pair[0] == 2).unwrap()[0] + 1;
+
+			assert_eq!(day5::part1(&data).unwrap(), *ids.last().unwrap(), "seed {}", seed);
+			assert_eq!(day5::part2(&data).unwrap(), missing, "seed {}", seed);
+		}
+	}
+
+	fn reference_bags(data: &[String]) -> HashMap<String, Vec<(i32, String)>> {
+		return data.iter().map(|line| {
+			let (outer, inner) = line.split_once(" bags contain ").unwrap();
+			let contents = inner.trim_end_matches('.').split(", ").filter(|item| *item != "no other bags").map(|item| {
+				let words: Vec<&str> = item.split(' ').collect();
+				(words[0].parse().unwrap(), format!("{} {}", words[1], words[2]))
+			}).collect();
+			(String::from(outer), contents)
+		}).collect();
+	}
+
+	fn reaches_gold(bag: &str, bags: &HashMap<String, Vec<(i32, String)>>) -> bool {
+		return bags[bag].iter().any(|(_, inner)| inner == "shiny gold" || reaches_gold(inner, bags));
+	}
+
+	fn bags_inside(bag: &str, bags: &HashMap<String, Vec<(i32, String)>>) -> i32 {
+		return bags[bag].iter().map(|(count, inner)| count * (1 + bags_inside(inner, bags))).sum();
+	}
+
+	#[test]
+	fn day7_matches_the_reference() {
+		for seed in 0..SEEDS {
+			let data = read_lines(&day7(&mut Rng::new(seed), 150));
+			let bags = reference_bags(&data);
+
+			assert_eq!(day7::part1(&data).unwrap(), bags.keys().filter(|bag| reaches_gold(bag, &bags)).count() as i32, "seed {}", seed);
+			assert_eq!(day7::part2(&data).unwrap(), bags_inside("shiny gold", &bags), "seed {}", seed);
+		}
+	}
+
+	// Runs until the program ends or an instruction comes round again, reporting the accumulator and which
+	fn reference_run(program: &[(String, i64)]) -> (i64, bool) {
+		let (mut pc, mut accumulator, mut seen) = (0i64, 0, HashSet::new());
+		while (pc as usize) < program.len() {
+			if !seen.insert(pc) { return (accumulator, false); }
+			match program[pc as usize].0.as_str() {
+				"acc" => { accumulator += program[pc as usize].1; pc += 1; },
+				"jmp" => pc += program[pc as usize].1,
+				_ => pc += 1
+			}
+		}
+
+		return (accumulator, true);
+	}
+
+	#[test]
+	fn day8_programs_loop() {
+		for seed in 0..2000 {
+			for size in &[5, 20, 300] {
+				let data = read_lines(&day8(&mut Rng::new(seed), *size));
+				let program: Vec<(String, i64)> = data.iter().map(|line| (String::from(&line[..3]), line[4..].parse().unwrap())).collect();
+				assert!(!reference_run(&program).1, "seed {} size {}", seed, size);
+			}
+		}
+	}
+
+	#[test]
+	fn day8_matches_the_reference() {
+		for seed in 0..SEEDS {
+			let data = read_lines(&day8(&mut Rng::new(seed), 300));
+			let program: Vec<(String, i64)> = data.iter().map(|line| (String::from(&line[..3]), line[4..].parse().unwrap())).collect();
+
+			let fixed = (0..program.len()).filter(|index| program[*index].0 != "acc").find_map(|index| {
+				let mut swapped = program.clone();
+				swapped[index].0 = String::from(if swapped[index].0 == "jmp" { "nop" } else { "jmp" });
+				match reference_run(&swapped) {
+					(accumulator, true) => Some(accumulator),
+					_ => None
+				}
+			});
+
+			assert_eq!(day8::part1(&data).unwrap(), reference_run(&program).0, "seed {}", seed);
+			assert_eq!(day8::part2(&data).unwrap(), fixed.unwrap(), "seed {}", seed);
+		}
+	}
+
+	fn floating_addresses(address: u64, mask: &str) -> Vec<u64> {
+		let mut addresses = vec![0];
+		for (bit, c) in mask.chars().enumerate().map(|(index, c)| (35 - index, c)) {
+			addresses = addresses.into_iter().flat_map(|partial: u64| match c {
+				'0' => vec![partial | (address & (1 << bit))],
+				'1' => vec![partial | (1 << bit)],
+				_ => vec![partial, partial | (1 << bit)]
+			}).collect();
+		}
+
+		return addresses;
+	}
+
+	#[test]
+	fn day14_matches_the_reference() {
+		for seed in 0..SEEDS {
+			let data = read_lines(&day14(&mut Rng::new(seed), 200));
+			let (mut first, mut second, mut mask) = (HashMap::new(), HashMap::new(), String::new());
+
+			for line in &data {
+				let (target, value) = line.split_once(" = ").unwrap();
+				if target == "mask" { mask = String::from(value); continue; }
+
+				let address: u64 = target[4..target.len() - 1].parse().unwrap();
+				let value: u64 = value.parse().unwrap();
+				let masked = mask.chars().enumerate().fold(value, |value, (index, c)| match c {
+					'0' => value & !(1 << (35 - index)),
+					'1' => value | (1 << (35 - index)),
+					_ => value
+				});
+
+				first.insert(address, masked);
+				for floating in floating_addresses(address, &mask) {
+					second.insert(floating, value);
+				}
+			}
+
+			assert_eq!(day14::part1(&data).unwrap(), first.values().map(|value| *value as u128).sum::<u128>(), "seed {}", seed);
+			assert_eq!(day14::part2(&data).unwrap(), second.values().map(|value| *value as u128).sum::<u128>(), "seed {}", seed);
+		}
+	}
+
+	fn permutations(items: Vec<usize>) -> Vec<Vec<usize>> {
+		if items.len() <= 1 { return vec![items]; }
+
+		return (0..items.len()).flat_map(|index| {
+			let mut rest = items.clone();
+			let first = rest.remove(index);
+			permutations(rest).into_iter().map(move |mut tail| { tail.insert(0, first); tail })
+		}).collect();
+	}
+
+	#[test]
+	fn day16_matches_the_reference() {
+		for seed in 0..SEEDS {
+			let data = read_records(&day16(&mut Rng::new(seed), 60));
+			let rules: Vec<(String, Vec<(usize, usize)>)> = data[0].iter().map(|line| {
+				let (name, ranges) = line.split_once(": ").unwrap();
+				let ranges = ranges.split(" or ").map(|range| {
+					let (low, high) = range.split_once('-').unwrap();
+					(low.parse().unwrap(), high.parse().unwrap())
+				}).collect();
+				(String::from(name), ranges)
+			}).collect();
+			let ticket = |line: &String| line.split(',').map(|value| value.parse().unwrap()).collect::<Vec<usize>>();
+			let accepts = |rule: usize, value: usize| rules[rule].1.iter().any(|(low, high)| (*low..=*high).contains(&value));
+
+			let mine = ticket(&data[1][1]);
+			let nearby: Vec<Vec<usize>> = data[2][1..].iter().map(ticket).collect();
+			let error_rate: usize = nearby.iter().flatten().filter(|value| !(0..rules.len()).any(|rule| accepts(rule, **value))).sum();
+			let valid: Vec<&Vec<usize>> = nearby.iter().filter(|values| values.iter().all(|value| (0..rules.len()).any(|rule| accepts(rule, *value)))).collect();
+
+			let assignments: Vec<Vec<usize>> = permutations((0..rules.len()).collect()).into_iter()
+				.filter(|order| valid.iter().all(|values| values.iter().zip(order).all(|(value, rule)| accepts(*rule, *value)))).collect();
+			assert_eq!(assignments.len(), 1, "seed {}", seed);
+			let departures: usize = assignments[0].iter().zip(&mine).filter(|(rule, _)| rules[**rule].0.starts_with("departure")).map(|(_, value)| value).product();
+
+			assert_eq!(day16::part1(&data).unwrap(), error_rate, "seed {}", seed);
+			assert_eq!(day16::part2(&data).unwrap(), departures, "seed {}", seed);
+		}
+	}
+
+	// Recursive descent, where the solver uses a shunting yard; `add_first` gives + precedence over *
+	fn reference_value(tokens: &mut std::iter::Peekable<std::vec::IntoIter<char>>, add_first: bool) -> i64 {
+		let term = |tokens: &mut std::iter::Peekable<std::vec::IntoIter<char>>| match tokens.next() {
+			Some('(') => {
+				let value = reference_value(tokens, add_first);
+				tokens.next();
+				value
+			},
+			Some(digit) => digit.to_digit(10).unwrap() as i64,
+			None => panic!("ran out of tokens")
+		};
+
+		let mut products = vec![term(tokens)];
+		while let Some(operator) = tokens.peek().copied().filter(|c| *c == '+' || *c == '*') {
+			tokens.next();
+			let value = term(tokens);
+			let last = products.last_mut().unwrap();
+			match (operator, add_first) {
+				('+', _) => *last += value,
+				('*', true) => products.push(value),
+				_ => *last *= value
+			}
+		}
+
+		return products.iter().product();
+	}
+
+	#[test]
+	fn day18_matches_the_reference() {
+		for seed in 0..SEEDS {
+			let data = read_lines(&day18(&mut Rng::new(seed), 100));
+			let evaluate = |add_first| data.iter().map(|line| reference_value(&mut line.chars().filter(|c| *c != ' ').collect::<Vec<_>>().into_iter().peekable(), add_first)).sum::<i64>();
+
+			assert_eq!(day18::part1(&data).unwrap(), evaluate(false), "seed {}", seed);
+			assert_eq!(day18::part2(&data).unwrap(), evaluate(true), "seed {}", seed);
+		}
+	}
+}
\ No newline at end of file
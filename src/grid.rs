@@ -0,0 +1,190 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+
+pub const ORTHOGONAL: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+pub const ADJACENT: [(i64, i64); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+// Cells are stored row by row; positions are (x, y) with y counting down from the first line
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+	width: usize,
+	height: usize,
+	cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+	pub fn new(width: usize, height: usize, fill: T) -> Grid<T> where T: Clone {
+		return Grid { width, height, cells: vec![fill; width * height] };
+	}
+
+	// Rows have to be non-empty and all the same width, and `cell` turns down any character it doesn't expect
+	pub fn parse<F>(data: &[String], mut cell: F) -> Result<Grid<T>> where F: FnMut(char) -> Option<T> {
+		let width = data.first().map(|line| line.chars().count()).unwrap_or(0);
+		if width == 0 { return Err(Error::at_line(1, "the grid has no columns")); }
+
+		let mut cells = Vec::with_capacity(width * data.len());
+		for (index, line) in data.iter().enumerate() {
+			let length = line.chars().count();
+			if length != width { return Err(Error::at_line(index + 1, format!("row is {} wide, expected {}", length, width))); }
+
+			for c in line.chars() {
+				cells.push(cell(c).ok_or_else(|| Error::at_line(index + 1, format!("unexpected character '{}'", c)))?);
+			}
+		}
+
+		return Ok(Grid { width, height: data.len(), cells });
+	}
+
+	pub fn width(&self) -> usize {
+		return self.width;
+	}
+
+	pub fn height(&self) -> usize {
+		return self.height;
+	}
+
+	pub fn contains(&self, x: i64, y: i64) -> bool {
+		return x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height;
+	}
+
+	pub fn get(&self, x: i64, y: i64) -> Option<&T> {
+		if !self.contains(x, y) { return None; }
+		return Some(&self.cells[y as usize * self.width + x as usize]);
+	}
+
+	pub fn get_mut(&mut self, x: i64, y: i64) -> Option<&mut T> {
+		if !self.contains(x, y) { return None; }
+		return Some(&mut self.cells[y as usize * self.width + x as usize]);
+	}
+
+	// The grid repeats forever in both directions, which is how the day 3 map works
+	pub fn get_wrapping(&self, x: i64, y: i64) -> &T {
+		let x = x.rem_euclid(self.width as i64) as usize;
+		let y = y.rem_euclid(self.height as i64) as usize;
+		return &self.cells[y * self.width + x];
+	}
+
+	pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+		let width = self.width;
+		return (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)));
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &T> {
+		return self.cells.iter();
+	}
+
+	pub fn map<U, F>(&self, f: F) -> Grid<U> where F: FnMut(&T) -> U {
+		return Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() };
+	}
+
+	// The cells one step away in each direction that are still on the grid; pass ORTHOGONAL or ADJACENT
+	pub fn neighbors<'a>(&self, x: usize, y: usize, directions: &'a [(i64, i64)]) -> impl Iterator<Item = (usize, usize)> + 'a {
+		let (width, height) = (self.width as i64, self.height as i64);
+		return directions.iter()
+			.map(move |(dx, dy)| (x as i64 + dx, y as i64 + dy))
+			.filter(move |(x, y)| *x >= 0 && *y >= 0 && *x < width && *y < height)
+			.map(|(x, y)| (x as usize, y as usize));
+	}
+
+	// Walks each direction until it reaches a cell that `visible` picks out, skipping over the rest; directions
+	// that run off the grid first see nothing
+	pub fn line_of_sight<F>(&self, x: usize, y: usize, directions: &[(i64, i64)], visible: F) -> Vec<(usize, usize)> where F: Fn(&T) -> bool {
+		let mut seen = vec![];
+		for (dx, dy) in directions {
+			let (mut x, mut y) = (x as i64 + dx, y as i64 + dy);
+			while let Some(cell) = self.get(x, y) {
+				if visible(cell) {
+					seen.push((x as usize, y as usize));
+					break;
+				}
+				x += dx;
+				y += dy;
+			}
+		}
+
+		return seen;
+	}
+
+	pub fn render<F>(&self, cell: F) -> String where F: Fn(&T) -> char {
+		let mut text = String::with_capacity((self.width + 1) * self.height);
+		for row in self.cells.chunks(self.width.max(1)) {
+			text.extend(row.iter().map(&cell));
+			text.push('\n');
+		}
+
+		return text;
+	}
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+	type Output = T;
+
+	fn index(&self, (x, y): (usize, usize)) -> &T {
+		assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} grid", x, y, self.width, self.height);
+		return &self.cells[y * self.width + x];
+	}
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+	fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+		assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} grid", x, y, self.width, self.height);
+		return &mut self.cells[y * self.width + x];
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn lines(text: &str) -> Vec<String> {
+		return text.lines().map(String::from).collect();
+	}
+
+	fn sample() -> Grid<char> {
+		return Grid::parse(&lines("#.L\n.L.\nL.#"), Some).unwrap();
+	}
+
+	#[test]
+	fn parsing_checks_the_shape() {
+		let grid = sample();
+		assert_eq!((grid.width(), grid.height()), (3, 3));
+		assert_eq!(grid[(2, 0)], 'L');
+		assert_eq!(grid.render(|c| *c), "#.L\n.L.\nL.#\n");
+
+		assert_eq!(Grid::<char>::parse(&[], Some).unwrap_err(), Error::at_line(1, "the grid has no columns"));
+		assert_eq!(Grid::parse(&lines("##\n#"), Some).unwrap_err(), Error::at_line(2, "row is 1 wide, expected 2"));
+		assert_eq!(Grid::parse(&lines("#.\n.x"), |c| if c == 'x' { None } else { Some(c) }).unwrap_err(), Error::at_line(2, "unexpected character 'x'"));
+	}
+
+	#[test]
+	fn access_is_checked_or_wraps() {
+		let mut grid = sample();
+		assert_eq!(grid.get(-1, 0), None);
+		assert_eq!(grid.get(0, 3), None);
+		assert_eq!(grid.get(2, 2), Some(&'#'));
+		assert_eq!(*grid.get_wrapping(-1, 5), '#');
+		assert_eq!(*grid.get_wrapping(4, 0), '.');
+
+		*grid.get_mut(1, 0).unwrap() = '#';
+		grid[(0, 0)] = '.';
+		assert_eq!(grid.render(|c| *c), ".#L\n.L.\nL.#\n");
+		assert_eq!(grid.map(|c| *c == '#').iter().filter(|tree| **tree).count(), 2);
+	}
+
+	#[test]
+	fn neighbors_stay_on_the_grid() {
+		let grid = sample();
+		assert_eq!(grid.neighbors(0, 0, &ORTHOGONAL).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+		assert_eq!(grid.neighbors(0, 0, &ADJACENT).count(), 3);
+		assert_eq!(grid.neighbors(1, 1, &ADJACENT).count(), 8);
+		assert_eq!(grid.positions().count(), 9);
+	}
+
+	#[test]
+	fn line_of_sight_skips_the_floor() {
+		let grid = Grid::parse(&lines("L...#\n.....\n..L.."), Some).unwrap();
+		assert_eq!(grid.line_of_sight(0, 0, &ADJACENT, |c| *c != '.'), vec![(4, 0), (2, 2)]);
+		assert_eq!(grid.line_of_sight(2, 2, &ORTHOGONAL, |c| *c != '.'), vec![]);
+	}
+}
\ No newline at end of file
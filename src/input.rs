@@ -0,0 +1,181 @@
+use std::{
+	fmt,
+	fs::read_to_string,
+	io::{self, Read},
+	path::PathBuf
+};
+
+use crate::error::{self, Error, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputKind {
+	Lines,
+	Chunk,
+	Numbers,
+	Records
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+	File(PathBuf),
+	Stdin
+}
+
+impl Source {
+	// "-" is the usual spelling for standard input
+	pub fn parse(value: &str) -> Source {
+		match value {
+			"-" => return Source::Stdin,
+			x => return Source::File(PathBuf::from(x))
+		}
+	}
+
+	pub fn read(&self) -> Result<String> {
+		let mut text = String::new();
+		let result = match self {
+			Source::File(path) => read_to_string(path).map(|data| text = data),
+			Source::Stdin => io::stdin().read_to_string(&mut text).map(|_| ())
+		};
+
+		return result.map(|_| text).map_err(|e| Error::new(format!("couldn't read {}: {}", self, e)));
+	}
+}
+
+impl fmt::Display for Source {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Source::File(path) => write!(f, "{}", path.display()),
+			Source::Stdin => write!(f, "standard input")
+		}
+	}
+}
+
+pub enum Input {
+	Lines(Vec<String>),
+	Chunk(String),
+	Numbers(Vec<i32>),
+	Records(Vec<Vec<String>>)
+}
+
+impl Input {
+	pub fn load(kind: InputKind, source: &Source) -> Result<Input> {
+		return Input::parse(kind, source.read()?);
+	}
+
+	pub fn parse(kind: InputKind, text: String) -> Result<Input> {
+		match kind {
+			InputKind::Lines => return Ok(Input::Lines(read_lines(&text))),
+			InputKind::Chunk => return Ok(Input::Chunk(read_chunk(&text))),
+			InputKind::Numbers => return Ok(Input::Numbers(read_lines_to_numbers(&text)?)),
+			InputKind::Records => return Ok(Input::Records(read_records(&text)))
+		}
+	}
+
+	// A mismatch here means a day was registered with the wrong input kind, so there's nothing sensible to recover to
+	pub fn lines(&self) -> &[String] {
+		match self {
+			Input::Lines(lines) => return lines,
+			_ => panic!("input was not loaded as lines")
+		}
+	}
+
+	pub fn chunk(&self) -> &str {
+		match self {
+			Input::Chunk(chunk) => return chunk,
+			_ => panic!("input was not loaded as a chunk")
+		}
+	}
+
+	pub fn numbers(&self) -> &[i32] {
+		match self {
+			Input::Numbers(numbers) => return numbers,
+			_ => panic!("input was not loaded as numbers")
+		}
+	}
+
+	pub fn records(&self) -> &[Vec<String>] {
+		match self {
+			Input::Records(records) => return records,
+			_ => panic!("input was not loaded as records")
+		}
+	}
+}
+
+pub fn read_lines_to_numbers(text: &str) -> Result<Vec<i32>> {
+	return error::parse_lines(&read_lines(text), error::parse_number::<i32>);
+}
+
+// Inputs can arrive with either line ending and with stray whitespace from an editor or a copy and paste, none of
+// which any puzzle cares about, so every loader sees lines with trailing whitespace and trailing blank lines removed
+pub fn read_lines(text: &str) -> Vec<String> {
+	let mut lines: Vec<String> = text.lines().map(|line| String::from(line.trim_end())).collect();
+	while lines.last().is_some_and(|line| line.is_empty()) {
+		lines.pop();
+	}
+
+	return lines;
+}
+
+pub fn read_chunk(text: &str) -> String {
+	return read_lines(text).join("\n");
+}
+
+// Records are runs of non-blank lines; several blank lines in a row still only separate two records
+pub fn read_records(text: &str) -> Vec<Vec<String>> {
+	let mut records = vec![];
+	let mut record = vec![];
+
+	for line in read_lines(text) {
+		match line.as_str() {
+			"" if record.is_empty() => (),
+			"" => records.push(std::mem::take(&mut record)),
+			_ => record.push(line)
+		}
+	}
+	if !record.is_empty() {
+		records.push(record);
+	}
+
+	return records;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sources_parse() {
+		assert_eq!(Source::parse("-"), Source::Stdin);
+		assert_eq!(Source::parse("data/day1.txt"), Source::File(PathBuf::from("data/day1.txt")));
+		assert_eq!(Source::Stdin.to_string(), "standard input");
+	}
+
+	#[test]
+	fn text_parses_into_each_kind() {
+		assert_eq!(Input::parse(InputKind::Numbers, String::from("1\n2\n")).unwrap().numbers(), &[1, 2]);
+		assert_eq!(Input::parse(InputKind::Lines, String::from("a\nb")).unwrap().lines(), &["a", "b"]);
+		assert_eq!(Input::parse(InputKind::Chunk, String::from("a\nb")).unwrap().chunk(), "a\nb");
+		assert_eq!(Input::parse(InputKind::Numbers, String::from("1\nx")).err().unwrap().to_string(), "line 2: 'x' is not a valid number");
+	}
+
+	#[test]
+	fn line_endings_and_trailing_whitespace_are_ignored() {
+		assert_eq!(read_lines("a \r\nb\t\r\n\r\n\n"), vec!["a", "b"]);
+		assert_eq!(read_chunk("0,3,6\r\n"), "0,3,6");
+		assert_eq!(read_chunk("a\r\n\r\nb  \n"), read_chunk("a\n\nb\n"));
+	}
+
+	#[test]
+	fn records_are_split_on_blank_lines() {
+		let expected = vec![vec!["a", "b"], vec!["c"]];
+		assert_eq!(read_records("a\nb\n\nc\n"), expected);
+		assert_eq!(read_records("\r\na\r\nb\r\n \r\n\r\nc"), expected);
+		assert!(read_records("").is_empty());
+	}
+
+	#[test]
+	fn missing_files_are_reported() {
+		let error = Input::load(InputKind::Lines, &Source::parse("data/nope.txt")).err().unwrap();
+		assert!(error.message.starts_with("couldn't read data/nope.txt"));
+	}
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+// Explicit returns are the house style
+#![allow(clippy::needless_return)]
+
+pub mod answers;
+pub mod batch;
+pub mod bench;
+pub mod biguint;
+pub mod cache;
+pub mod cancel;
+pub mod checked;
+pub mod error;
+pub mod fixtures;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod memory;
+pub mod progress;
+pub mod results;
+pub mod runner;
+pub mod solver;
+pub mod visualize;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
\ No newline at end of file
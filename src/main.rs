@@ -1,140 +1,260 @@
-use std::{
-  fs::{File, read_to_string},
-  io::{prelude::*, BufReader},
-	path::Path
-};
-use tokio::task;
-use futures::future::join_all;
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-
-struct DayResults {
-	day:  i32,
-	part1: String,
-	part2: String
+// Explicit returns are the house style
+#![allow(clippy::needless_return)]
+
+use advent2020::{answers, batch, bench, generate, runner, solver};
+use advent2020::cache::{self, Cache};
+use advent2020::results::DayResults;
+use advent2020::visualize::Target;
+
+// Counting every allocation costs a little on each one, so it's only built in when asked for
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: advent2020::memory::Counting = advent2020::memory::Counting;
+
+mod cli;
+mod output;
+mod report;
+mod scaffold;
+mod serve;
+mod watch;
+
+#[tokio::main]
+async fn main() {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	if args.first().map(String::as_str) == Some("new-day") {
+		new_day(args.into_iter().skip(1));
+		return;
+	}
+	if args.first().map(String::as_str) == Some("generate") {
+		generate(args.into_iter().skip(1));
+		return;
+	}
+	if args.first().map(String::as_str) == Some("serve") {
+		serve(args.into_iter().skip(1)).await;
+		return;
+	}
+
+	let available: Vec<i32> = solver::solvers().iter().map(|solver| solver.day()).collect();
+	let options = match cli::parse_args(args, &available, std::env::var("AOC_DATA_DIR").ok()) {
+		Ok(options) => options,
+		Err(message) => {
+			eprintln!("{}\n\n{}", message, cli::USAGE);
+			std::process::exit(2);
+		}
+	};
+
+	if options.help {
+		println!("{}", cli::USAGE);
+		return;
+	}
+
+	if options.list {
+		for solver in solver::solvers() {
+			println!("Day {}: {}", solver.day(), solver.name());
+		}
+		return;
+	}
+
+	if options.bench {
+		run_benchmarks(&options);
+		return;
+	}
+
+	if options.watch {
+		watch::watch(&options).await;
+		return;
+	}
+
+	if let Some(dir) = &options.batch {
+		run_batch(&options, dir).await;
+		return;
+	}
+
+	// Only days picked on the command line get run at all, so a slow day that wasn't asked for costs nothing
+	let days = options.days.iter().filter_map(|day| solver::find(*day)).map(|solver| (solver, options.source(solver.day()))).collect();
+	let cache = if options.cache { Some(Cache::new(cache::DEFAULT_DIR)) } else { None };
+	let results = runner::run_days(days, &options.parts, options.jobs, options.timeout, cache, options.visualize.clone(), show_progress(&options)).await;
+
+	if !options.reports.is_empty() {
+		write_reports(&results, &options);
+	}
+
+	if options.check {
+		check_results(&results, &options.answers);
+		return;
+	}
+
+	print!("{}", output::render(&results, options.format));
+
+	if runner::any_failed(&results) {
+		std::process::exit(1);
+	}
+}
+
+// Progress lines are only drawn for a person watching plain text output, and not over frames the terminal
+// visualizer is drawing
+fn show_progress(options: &cli::Options) -> bool {
+	use std::io::IsTerminal;
+	return options.format == output::Format::Text && options.visualize != Some(Target::Terminal) && std::io::stderr().is_terminal();
 }
 
-macro_rules! day_results {
-	($day_mod:ident, $day_value:expr, $data:expr) => {
-		DayResults { day: $day_value, part1: $day_mod::part1($data).to_string(), part2: $day_mod::part2($data).to_string() };
+async fn run_batch(options: &cli::Options, dir: &std::path::Path) {
+	let users = match batch::users(dir) {
+		Ok(users) => users,
+		Err(e) => {
+			eprintln!("{}", e);
+			std::process::exit(2);
+		}
+	};
+
+	let solvers: Vec<_> = options.days.iter().filter_map(|day| solver::find(*day)).collect();
+	let cache = if options.cache { Some(Cache::new(cache::DEFAULT_DIR)) } else { None };
+	let matrix = batch::run(&users, &solvers, &options.parts, options.jobs, options.timeout, cache, show_progress(options)).await;
+
+	print!("{}", output::render_batch(&matrix, options.format));
+
+	// The text table already lists these; the other formats are for machines, so the warning goes to stderr
+	let mixed = matrix.mixed_panics();
+	if options.format != output::Format::Text {
+		for panic in &mixed {
+			eprintln!("{}", output::mixed_panic_line(panic));
+		}
+	}
+
+	if !mixed.is_empty() || matrix.results().any(|(_, _, part)| part.status.error().is_some()) {
+		std::process::exit(1);
 	}
 }
 
-#[tokio::main]
-async fn main() {
-	let mut day_futures = [
-		task::spawn(async {
-			let data = read_lines_to_numbers("data/day1.txt");
-			return day_results!(day1, 1, &data);
-			//return DayResults { day: 1, part1: day1::part1(&day1_data).to_string(), part2: day1::part2(&day1_data).to_string() };
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day2.txt");
-			return day_results!(day2, 2, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day3.txt");
-			return day_results!(day3, 3, &data);
-		}),
-		task::spawn(async {
-			let data = read_chunk("data/day4.txt");
-			return day_results!(day4, 4, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day5.txt");
-			return day_results!(day5, 5, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day6.txt");
-			return day_results!(day6, 6, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day7.txt");
-			return day_results!(day7, 7, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day8.txt");
-			return day_results!(day8, 8, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day9.txt");
-			return day_results!(day9, 9, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day10.txt");
-			return day_results!(day10, 10, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day11.txt");
-			return day_results!(day11, 11, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day12.txt");
-			return day_results!(day12, 12, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day13.txt");
-			return day_results!(day13, 13, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day14.txt");
-			return day_results!(day14, 14, &data);
-		}),
-		task::spawn(async {
-			let data = read_chunk("data/day15.txt");
-			return day_results!(day15, 15, &data);
-		}),
-		task::spawn(async {
-			let data = read_chunk("data/day16.txt");
-			return day_results!(day16, 16, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day17.txt");
-			return day_results!(day17, 17, &data);
-		}),
-		task::spawn(async {
-			let data = read_lines("data/day18.txt");
-			return day_results!(day18, 18, &data);
-		})
-	];
-
-	let wrapped_results = join_all(day_futures.iter_mut()).await;
-	let mut results: Vec<&DayResults> = wrapped_results.iter().flatten().collect();
-	results.sort_by(|a, b| a.day.partial_cmp(&b.day).unwrap());
-
-	for result in &results {
-		println!("Day {} part 1 result: {}", result.day, result.part1);
-		println!("Day {} part 2 result: {}", result.day, result.part2);
+async fn serve<I>(args: I) where I: IntoIterator<Item = String> {
+	let options = match cli::parse_serve(args) {
+		Ok(options) => options,
+		Err(message) => {
+			eprintln!("{}\n\n{}", message, cli::USAGE);
+			std::process::exit(2);
+		}
+	};
+
+	let listener = match tokio::net::TcpListener::bind(&options.address).await {
+		Ok(listener) => listener,
+		Err(e) => {
+			eprintln!("couldn't listen on {}: {}", options.address, e);
+			std::process::exit(1);
+		}
+	};
+
+	match listener.local_addr() {
+		Ok(address) => eprintln!("listening on http://{}", address),
+		Err(_) => eprintln!("listening on http://{}", options.address)
 	}
+	serve::serve(listener, serve::Limits::new(options.timeout, options.jobs)).await;
 }
 
-fn read_lines_to_numbers(filename: impl AsRef<Path>) -> Vec<i32> {
-	return read_lines(filename).iter().map(|line| line.parse::<i32>().unwrap()).collect();
+fn generate<I>(args: I) where I: IntoIterator<Item = String> {
+	let options = match cli::parse_generate(args) {
+		Ok(options) => options,
+		Err(message) => {
+			eprintln!("{}\n\n{}", message, cli::USAGE);
+			std::process::exit(2);
+		}
+	};
+
+	// Without a seed each run gets a different input, so say which one it was in case it needs replaying
+	let seed = options.seed.unwrap_or_else(|| {
+		let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+		let seed = now.as_nanos() as u64;
+		eprintln!("seed {}", seed);
+		seed
+	});
+
+	match generate::generate(options.day, &mut generate::Rng::new(seed), options.size) {
+		Ok(text) => print!("{}", text),
+		Err(e) => {
+			eprintln!("{}", e);
+			std::process::exit(2);
+		}
+	}
 }
 
-fn read_lines(filename: impl AsRef<Path>) -> Vec<String> {
-	let file = File::open(filename).expect("no such file");
-	let buf = BufReader::new(file);
-	return buf.lines().map(|line| line.unwrap()).collect();
+fn new_day<I>(args: I) where I: IntoIterator<Item = String> {
+	let options = match cli::parse_new_day(args) {
+		Ok(options) => options,
+		Err(message) => {
+			eprintln!("{}\n\n{}", message, cli::USAGE);
+			std::process::exit(2);
+		}
+	};
+
+	if solver::find(options.day).is_some() {
+		eprintln!("day {} is already registered", options.day);
+		std::process::exit(2);
+	}
+
+	match scaffold::new_day(std::path::Path::new("."), options.day, &options.name, options.kind) {
+		Ok(touched) => touched.iter().for_each(|path| println!("wrote {}", path.display())),
+		Err(message) => {
+			eprintln!("{}", message);
+			std::process::exit(1);
+		}
+	}
 }
 
-fn read_chunk(filename: impl AsRef<Path>) -> String {
-	return read_to_string(filename).unwrap();
+// Reports still get written without an answers file, they just can't say whether anything passed
+fn write_reports(results: &[DayResults], options: &cli::Options) {
+	let answers = if std::path::Path::new(&options.answers).exists() {
+		match answers::Answers::load(&options.answers) {
+			Ok(answers) => answers,
+			Err(message) => {
+				eprintln!("{}", message);
+				std::process::exit(2);
+			}
+		}
+	} else {
+		answers::Answers::default()
+	};
+
+	for (kind, path) in &options.reports {
+		if let Err(e) = std::fs::write(path, report::render(results, &answers, *kind)) {
+			eprintln!("couldn't write {}: {}", path.display(), e);
+			std::process::exit(1);
+		}
+	}
+}
+
+fn check_results(results: &[DayResults], filename: &str) {
+	let answers = match answers::Answers::load(filename) {
+		Ok(answers) => answers,
+		Err(message) => {
+			eprintln!("{}", message);
+			std::process::exit(2);
+		}
+	};
+
+	let checks = answers.check(results);
+	print!("{}", answers::render(&checks));
+
+	if checks.iter().any(|check| check.verdict == answers::Verdict::Fail) {
+		std::process::exit(1);
+	}
 }
+
+// Benchmarks run one day at a time so the timings aren't competing with other solvers for the CPU
+fn run_benchmarks(options: &cli::Options) {
+	let mut results = vec![];
+	let mut failed = false;
+	for solver in options.days.iter().filter_map(|day| solver::find(*day)) {
+		match bench::bench_day(solver, &options.parts, &options.source(solver.day()), options.iterations) {
+			Ok(result) => results.push(result),
+			Err(e) => {
+				eprintln!("{}", e.with_day(solver.day()));
+				failed = true;
+			}
+		}
+	}
+
+	print!("{}", bench::render(&results));
+
+	if failed || results.iter().any(|result| result.any_failed()) {
+		std::process::exit(1);
+	}
+}
\ No newline at end of file
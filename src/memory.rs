@@ -0,0 +1,144 @@
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	cell::Cell
+};
+
+// Only bytes allocated and freed on the measuring thread count, so a part's numbers aren't mixed up with whatever
+// the runtime's other threads happen to be doing
+thread_local! {
+	static LIVE: Cell<i64> = const { Cell::new(0) };
+	static PEAK: Cell<i64> = const { Cell::new(0) };
+	static TOTAL: Cell<u64> = const { Cell::new(0) };
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Usage {
+	pub peak: u64,
+	pub total: u64
+}
+
+impl Usage {
+	pub fn max(self, other: Usage) -> Usage {
+		return Usage { peak: self.peak.max(other.peak), total: self.total.max(other.total) };
+	}
+}
+
+// The binary installs this as the global allocator when built with the memory feature
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		let pointer = System.alloc(layout);
+		if !pointer.is_null() { allocated(layout.size()); }
+		return pointer;
+	}
+
+	unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+		let pointer = System.alloc_zeroed(layout);
+		if !pointer.is_null() { allocated(layout.size()); }
+		return pointer;
+	}
+
+	unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+		System.dealloc(pointer, layout);
+		freed(layout.size());
+	}
+
+	// Counted as freeing the old block and allocating the new one, which is what a move would cost at worst
+	unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		let new_pointer = System.realloc(pointer, layout, new_size);
+		if !new_pointer.is_null() {
+			freed(layout.size());
+			allocated(new_size);
+		}
+		return new_pointer;
+	}
+}
+
+// These run inside the allocator, so they mustn't allocate or panic; the counters have no destructors, which keeps
+// the thread locals from needing to register one
+fn allocated(size: usize) {
+	let _ = LIVE.try_with(|live| {
+		live.set(live.get() + size as i64);
+		let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+	});
+	let _ = TOTAL.try_with(|total| total.set(total.get() + size as u64));
+}
+
+fn freed(size: usize) {
+	let _ = LIVE.try_with(|live| live.set(live.get() - size as i64));
+}
+
+pub fn enabled() -> bool {
+	return cfg!(feature = "memory");
+}
+
+// Without the counting allocator installed there's nothing to report
+pub fn measure<T, F>(f: F) -> (T, Option<Usage>) where F: FnOnce() -> T {
+	if !enabled() { return (f(), None); }
+
+	let (result, usage) = track(f);
+	return (result, Some(usage));
+}
+
+// The peak is measured from what was live when `f` started; measurements can nest, the outer one still sees the
+// inner peak
+fn track<T, F>(f: F) -> (T, Usage) where F: FnOnce() -> T {
+	let start = LIVE.with(|live| live.get());
+	let start_total = TOTAL.with(|total| total.get());
+	let outer_peak = PEAK.with(|peak| peak.replace(start));
+
+	let result = f();
+
+	let peak = PEAK.with(|peak| peak.get());
+	PEAK.with(|current| current.set(outer_peak.max(peak)));
+	let usage = Usage { peak: (peak - start).max(0) as u64, total: TOTAL.with(|total| total.get()) - start_total };
+
+	return (result, usage);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn tracking_counts_peak_and_total() {
+		let ((), usage) = track(|| {
+			allocated(100);
+			allocated(50);
+			freed(100);
+			allocated(20);
+			freed(70);
+		});
+
+		assert_eq!(usage, Usage { peak: 150, total: 170 });
+	}
+
+	#[test]
+	fn nested_tracking_reaches_the_outer_peak() {
+		let (inner, outer) = track(|| {
+			allocated(10);
+			let ((), inner) = track(|| {
+				allocated(40);
+				freed(40);
+			});
+			freed(10);
+			inner
+		});
+
+		assert_eq!(inner, Usage { peak: 40, total: 40 });
+		assert_eq!(outer, Usage { peak: 50, total: 50 });
+	}
+
+	#[test]
+	fn the_allocator_counts_what_goes_through_it() {
+		let layout = Layout::from_size_align(64, 8).unwrap();
+		let ((), usage) = track(|| unsafe {
+			let pointer = Counting.alloc(layout);
+			let pointer = Counting.realloc(pointer, layout, 128);
+			Counting.dealloc(pointer, Layout::from_size_align(128, 8).unwrap());
+		});
+
+		assert_eq!(usage, Usage { peak: 128, total: 192 });
+	}
+}
\ No newline at end of file
@@ -0,0 +1,242 @@
+use advent2020::batch::{Matrix, MixedPanic};
+use advent2020::results::{DayResults, PartResult, Status};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+	Text,
+	Json,
+	Csv
+}
+
+impl Format {
+	pub fn parse(value: &str) -> Option<Format> {
+		match value {
+			"text" => return Some(Format::Text),
+			"json" => return Some(Format::Json),
+			"csv" => return Some(Format::Csv),
+			_ => return None
+		}
+	}
+}
+
+pub fn render(results: &[DayResults], format: Format) -> String {
+	let rows = results.iter().flat_map(|day| day.parts.iter().map(move |part| (day.day, part)));
+
+	match format {
+		Format::Text => return rows.map(|(day, part)| text_row(day, part)).collect(),
+		Format::Json => {
+			let objects: Vec<String> = rows.map(|(day, part)| json_row(day, part)).collect();
+			if objects.is_empty() { return String::from("[]\n"); }
+			return format!("[\n{}\n]\n", objects.join(",\n"));
+		},
+		Format::Csv => {
+			let header = String::from("day,part,answer,elapsed_ms,status,error,cached\n");
+			return header + &rows.map(|(day, part)| csv_row(day, part)).collect::<String>();
+		}
+	}
+}
+
+// Text is a table with a column per user, followed by any errors and solvers that only panicked for some users;
+// JSON and CSV are the usual rows with the user added at the front
+pub fn render_batch(matrix: &Matrix, format: Format) -> String {
+	match format {
+		Format::Text => return batch_table(matrix),
+		Format::Json => {
+			let objects: Vec<String> = matrix.results().map(|(user, day, part)| format!("  {{\"user\": {}, {}", json_string(user), &json_object(day, part)[1..])).collect();
+			if objects.is_empty() { return String::from("[]\n"); }
+			return format!("[\n{}\n]\n", objects.join(",\n"));
+		},
+		Format::Csv => {
+			let header = String::from("user,day,part,answer,elapsed_ms,status,error,cached\n");
+			return header + &matrix.results().map(|(user, day, part)| format!("{},{}", csv_field(user), csv_row(day, part))).collect::<String>();
+		}
+	}
+}
+
+fn batch_cell(cell: &Option<PartResult>) -> String {
+	match cell {
+		None => return String::from("-"),
+		Some(part) if part.status == Status::Solved => return format!("{} ({} ms)", part.answer, elapsed_ms(part)),
+		Some(part) if part.status.panicked() => return String::from("PANICKED"),
+		Some(PartResult { status: Status::TimedOut(_), .. }) => return String::from("TIMED OUT"),
+		Some(_) => return String::from("FAILED")
+	}
+}
+
+fn batch_table(matrix: &Matrix) -> String {
+	let mixed = matrix.mixed_panics();
+	let mut lines: Vec<Vec<String>> = vec![[" ", "day", "part"].iter().map(|header| String::from(*header)).chain(matrix.users.iter().cloned()).collect()];
+	for row in &matrix.rows {
+		let flag = if mixed.iter().any(|panic| panic.day == row.day && panic.part == row.part) { "!" } else { " " };
+		lines.push(vec![String::from(flag), row.day.to_string(), row.part.to_string()].into_iter().chain(row.cells.iter().map(batch_cell)).collect());
+	}
+
+	let widths: Vec<usize> = (0..lines[0].len()).map(|column| lines.iter().map(|line| line[column].chars().count()).max().unwrap_or(0)).collect();
+	let mut text = String::new();
+	for line in &lines {
+		let cells: Vec<String> = line.iter().zip(&widths).enumerate().map(|(column, (cell, width))| match column {
+			1 | 2 => format!("{:>width$}", cell, width = width),
+			_ => format!("{:<width$}", cell, width = width)
+		}).collect();
+		text += cells.join("  ").trim_end();
+		text.push('\n');
+	}
+
+	let errors: Vec<String> = matrix.results().filter_map(|(user, _, part)| part.status.error().map(|error| format!("{} (part {}): {}\n", user, part.part, error))).collect();
+	if !errors.is_empty() || !mixed.is_empty() { text.push('\n'); }
+	text += &errors.concat();
+	text += &mixed.iter().map(|panic| mixed_panic_line(panic) + "\n").collect::<String>();
+
+	return text;
+}
+
+pub fn mixed_panic_line(panic: &MixedPanic) -> String {
+	return format!("! day {} part {} panicked for {} but not for {}", panic.day, panic.part, panic.panicked.join(", "), panic.survived.join(", "));
+}
+
+pub fn elapsed_ms(part: &PartResult) -> String {
+	return format!("{:.3}", part.elapsed.as_secs_f64() * 1000.0);
+}
+
+fn text_row(day: i32, part: &PartResult) -> String {
+	match &part.status {
+		Status::Solved if part.cached => return format!("Day {} part {} result: {} (cached)\n", day, part.part, part.answer),
+		Status::Solved => return format!("Day {} part {} result: {}\n", day, part.part, part.answer),
+		Status::Failed(error) => return format!("Day {} part {} FAILED: {}\n", day, part.part, error),
+		Status::TimedOut(error) => return format!("Day {} part {} TIMED OUT: {}\n", day, part.part, error)
+	}
+}
+
+fn json_row(day: i32, part: &PartResult) -> String {
+	return format!("  {}", json_object(day, part));
+}
+
+pub fn json_object(day: i32, part: &PartResult) -> String {
+	let error = part.status.error().map(json_string).unwrap_or_else(|| String::from("null"));
+	return format!("{{\"day\": {}, \"part\": {}, \"answer\": {}, \"elapsed_ms\": {}, \"status\": {}, \"error\": {}, \"cached\": {}}}",
+		day, part.part, json_string(&part.answer), elapsed_ms(part), json_string(part.status.label()), error, part.cached);
+}
+
+fn csv_row(day: i32, part: &PartResult) -> String {
+	let error = part.status.error().map(csv_field).unwrap_or_default();
+	return format!("{},{},{},{},{},{},{}\n", day, part.part, csv_field(&part.answer), elapsed_ms(part), part.status.label(), error, part.cached);
+}
+
+pub fn json_string(value: &str) -> String {
+	let mut escaped = String::from("\"");
+	for c in value.chars() {
+		match c {
+			'"' => escaped.push_str("\\\""),
+			'\\' => escaped.push_str("\\\\"),
+			'\n' => escaped.push_str("\\n"),
+			'\r' => escaped.push_str("\\r"),
+			'\t' => escaped.push_str("\\t"),
+			c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+			c => escaped.push(c)
+		}
+	}
+	escaped.push('"');
+	return escaped;
+}
+
+fn csv_field(value: &str) -> String {
+	if !value.contains([',', '"', '\n', '\r']) { return String::from(value); }
+	return format!("\"{}\"", value.replace('"', "\"\""));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+	use advent2020::batch::Row;
+
+	fn results() -> Vec<DayResults> {
+		let part = |part, answer: &str| PartResult { part, answer: String::from(answer), elapsed: Duration::from_micros(1500), status: Status::Solved, cached: false };
+		return vec![DayResults { day: 8, parts: vec![part(1, "5"), part(2, "say \"hi\", bye")] }];
+	}
+
+	fn failed() -> Vec<DayResults> {
+		let status = Status::Failed(String::from("day 8, line 3: unknown operation 'hcf'"));
+		return vec![DayResults { day: 8, parts: vec![PartResult { part: 1, answer: String::new(), elapsed: Duration::from_micros(1500), status, cached: false }] }];
+	}
+
+	#[test]
+	fn text_matches_the_old_output() {
+		assert_eq!(render(&results(), Format::Text), "Day 8 part 1 result: 5\nDay 8 part 2 result: say \"hi\", bye\n");
+	}
+
+	#[test]
+	fn text_reports_failures() {
+		assert_eq!(render(&failed(), Format::Text), "Day 8 part 1 FAILED: day 8, line 3: unknown operation 'hcf'\n");
+
+		let mut timed_out = failed();
+		timed_out[0].parts[0].status = Status::TimedOut(String::from("day 8: no answer within 2s"));
+		assert_eq!(render(&timed_out, Format::Text), "Day 8 part 1 TIMED OUT: day 8: no answer within 2s\n");
+		assert!(render(&timed_out, Format::Csv).ends_with(",timeout,day 8: no answer within 2s,false\n"));
+	}
+
+	#[test]
+	fn cached_answers_are_marked() {
+		let mut cached = results();
+		cached[0].parts[0].cached = true;
+		assert_eq!(render(&cached, Format::Text), "Day 8 part 1 result: 5 (cached)\nDay 8 part 2 result: say \"hi\", bye\n");
+		assert!(render(&cached, Format::Json).contains("\"status\": \"ok\", \"error\": null, \"cached\": true}"));
+	}
+
+	#[test]
+	fn json_works() {
+		assert_eq!(render(&results(), Format::Json), "[
+  {\"day\": 8, \"part\": 1, \"answer\": \"5\", \"elapsed_ms\": 1.500, \"status\": \"ok\", \"error\": null, \"cached\": false},
+  {\"day\": 8, \"part\": 2, \"answer\": \"say \\\"hi\\\", bye\", \"elapsed_ms\": 1.500, \"status\": \"ok\", \"error\": null, \"cached\": false}
+]
+");
+		assert_eq!(render(&[], Format::Json), "[]\n");
+		assert_eq!(render(&failed(), Format::Json), "[
+  {\"day\": 8, \"part\": 1, \"answer\": \"\", \"elapsed_ms\": 1.500, \"status\": \"failed\", \"error\": \"day 8, line 3: unknown operation 'hcf'\", \"cached\": false}
+]
+");
+	}
+
+	fn matrix() -> Matrix {
+		let part = |status, answer: &str| Some(PartResult { part: 1, answer: String::from(answer), elapsed: Duration::from_micros(1500), status, cached: false });
+		return Matrix {
+			users: vec![String::from("alice"), String::from("bob")],
+			rows: vec![
+				Row { day: 8, part: 1, cells: vec![part(Status::Solved, "5"), part(Status::Failed(String::from("day 8: panicked: oops")), "")] },
+				Row { day: 10, part: 1, cells: vec![None, part(Status::Solved, "12345")] }
+			]
+		};
+	}
+
+	#[test]
+	fn batches_render_as_a_table() {
+		assert_eq!(render_batch(&matrix(), Format::Text), "   day  part  alice         bob
+!    8     1  5 (1.500 ms)  PANICKED
+    10     1  -             12345 (1.500 ms)
+
+bob (part 1): day 8: panicked: oops
+! day 8 part 1 panicked for bob but not for alice
+");
+	}
+
+	#[test]
+	fn batches_render_as_rows() {
+		assert!(render_batch(&matrix(), Format::Json).starts_with("[\n  {\"user\": \"alice\", \"day\": 8, \"part\": 1, \"answer\": \"5\""));
+		assert_eq!(render_batch(&matrix(), Format::Csv), "user,day,part,answer,elapsed_ms,status,error,cached
+alice,8,1,5,1.500,ok,,false
+bob,8,1,,1.500,failed,day 8: panicked: oops,false
+bob,10,1,12345,1.500,ok,,false
+");
+	}
+
+	#[test]
+	fn csv_works() {
+		assert_eq!(render(&results(), Format::Csv), "day,part,answer,elapsed_ms,status,error,cached
+8,1,5,1.500,ok,,false
+8,2,\"say \"\"hi\"\", bye\",1.500,ok,,false
+");
+		assert_eq!(render(&failed(), Format::Csv), "day,part,answer,elapsed_ms,status,error,cached
+8,1,,1.500,failed,\"day 8, line 3: unknown operation 'hcf'\",false
+");
+	}
+}
\ No newline at end of file
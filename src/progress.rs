@@ -0,0 +1,183 @@
+use std::{
+	cell::RefCell,
+	io::{self, Write},
+	sync::{Arc, Mutex},
+	time::Instant
+};
+
+const BAR_WIDTH: usize = 30;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+	pub day: i32,
+	pub part: i32,
+	pub step: u64,
+	pub total: u64,
+	pub started: Instant
+}
+
+// Every part that's running has an entry here while it runs, so the runner can draw them from another thread
+#[derive(Clone, Debug, Default)]
+pub struct Board(Arc<Mutex<Vec<Entry>>>);
+
+impl Board {
+	pub fn new() -> Board {
+		return Board::default();
+	}
+
+	pub fn active(&self) -> Vec<Entry> {
+		let mut entries = self.lock().clone();
+		entries.sort_by_key(|entry| (entry.day, entry.part));
+		return entries;
+	}
+
+	fn start(&self, day: i32, part: i32) {
+		self.lock().push(Entry { day, part, step: 0, total: 0, started: Instant::now() });
+	}
+
+	fn update(&self, day: i32, part: i32, step: u64, total: u64) {
+		if let Some(entry) = self.lock().iter_mut().find(|entry| entry.day == day && entry.part == part) {
+			entry.step = step.min(total);
+			entry.total = total;
+		}
+	}
+
+	fn finish(&self, day: i32, part: i32) {
+		self.lock().retain(|entry| entry.day != day || entry.part != part);
+	}
+
+	// A solver that panicked while reporting can't have left an entry half written, so a poisoned lock is still usable
+	fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Entry>> {
+		return self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+	}
+}
+
+thread_local! {
+	static BOARD: RefCell<Option<Board>> = const { RefCell::new(None) };
+	static CURRENT: RefCell<Option<(Board, i32, i32)>> = const { RefCell::new(None) };
+}
+
+// The runner sets the board for each day's thread, the same way it hands out cancellation tokens
+pub fn with_board<T, F>(board: Option<&Board>, f: F) -> T where F: FnOnce() -> T {
+	let previous = BOARD.with(|current| current.replace(board.cloned()));
+	let result = f();
+	BOARD.with(|current| *current.borrow_mut() = previous);
+
+	return result;
+}
+
+pub fn for_part<T, F>(day: i32, part: i32, f: F) -> T where F: FnOnce() -> T {
+	let board = match BOARD.with(|current| current.borrow().clone()) {
+		Some(board) => board,
+		None => return f()
+	};
+
+	board.start(day, part);
+	let previous = CURRENT.with(|current| current.replace(Some((board.clone(), day, part))));
+	let _running = Running { board, day, part, previous };
+
+	return f();
+}
+
+// Cleans up in drop because the runner catches a panicking part and carries on, and its entry mustn't linger
+struct Running {
+	board: Board,
+	day: i32,
+	part: i32,
+	previous: Option<(Board, i32, i32)>
+}
+
+impl Drop for Running {
+	fn drop(&mut self) {
+		let previous = self.previous.take();
+		CURRENT.with(|current| *current.borrow_mut() = previous);
+		self.board.finish(self.day, self.part);
+	}
+}
+
+// Takes a lock when something is listening, so solvers in tight loops should only report every few thousand steps
+pub fn report(step: u64, total: u64) {
+	CURRENT.with(|current| {
+		if let Some((board, day, part)) = current.borrow().as_ref() {
+			board.update(*day, *part, step, total);
+		}
+	});
+}
+
+// One line per running part; parts that haven't reported anything yet only show how long they've been going
+pub fn render(entries: &[Entry], now: Instant) -> Vec<String> {
+	return entries.iter().map(|entry| {
+		let elapsed = now.saturating_duration_since(entry.started).as_secs_f64();
+		if entry.total == 0 { return format!("day {:>2} part {}  {:.1}s", entry.day, entry.part, elapsed); }
+
+		let filled = (entry.step as u128 * BAR_WIDTH as u128 / entry.total as u128) as usize;
+		let percent = entry.step as u128 * 100 / entry.total as u128;
+		return format!("day {:>2} part {}  [{}{}] {:>3}%  {:.1}s", entry.day, entry.part, "#".repeat(filled), ".".repeat(BAR_WIDTH - filled), percent, elapsed);
+	}).collect();
+}
+
+// Moves back over the `previous` lines drawn last time and replaces them; returns how many lines are on screen now
+pub fn redraw(out: &mut dyn Write, lines: &[String], previous: usize) -> io::Result<usize> {
+	let mut text = String::new();
+	if previous > 0 { text += &format!("\x1b[{}A", previous); }
+	text += "\r\x1b[J";
+	for line in lines {
+		text += line;
+		text.push('\n');
+	}
+
+	out.write_all(text.as_bytes())?;
+	out.flush()?;
+	return Ok(lines.len());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	#[test]
+	fn reports_follow_the_current_part() {
+		let board = Board::new();
+		report(1, 2);
+
+		with_board(Some(&board), || {
+			for_part(15, 2, || {
+				report(5, 10);
+				report(20, 10);
+				assert_eq!(board.active().iter().map(|entry| (entry.day, entry.part, entry.step, entry.total)).collect::<Vec<_>>(), vec![(15, 2, 10, 10)]);
+			});
+			assert!(board.active().is_empty());
+		});
+
+		let panicked = std::panic::catch_unwind(|| with_board(Some(&board), || for_part(17, 1, || panic!("oops"))));
+		assert!(panicked.is_err());
+		assert!(board.active().is_empty());
+
+		// Without a board nothing is recorded, but the part still runs
+		assert_eq!(for_part(15, 1, || 3), 3);
+		assert!(board.active().is_empty());
+	}
+
+	#[test]
+	fn entries_render_as_bars() {
+		let started = Instant::now();
+		let entries = vec![
+			Entry { day: 15, part: 2, step: 3, total: 10, started },
+			Entry { day: 8, part: 1, step: 0, total: 0, started }
+		];
+
+		assert_eq!(render(&entries, started + Duration::from_millis(2500)), vec![
+			"day 15 part 2  [#########.....................]  30%  2.5s",
+			"day  8 part 1  2.5s"
+		]);
+	}
+
+	#[test]
+	fn redraws_replace_the_previous_lines() {
+		let mut out = vec![];
+		assert_eq!(redraw(&mut out, &[String::from("a"), String::from("b")], 0).unwrap(), 2);
+		assert_eq!(redraw(&mut out, &[], 2).unwrap(), 0);
+		assert_eq!(String::from_utf8(out).unwrap(), "\r\x1b[Ja\nb\n\x1b[2A\r\x1b[J");
+	}
+}
\ No newline at end of file
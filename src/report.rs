@@ -0,0 +1,218 @@
+use std::path::Path;
+
+use advent2020::answers::{Answers, Verdict};
+use advent2020::results::{DayResults, PartResult, Status};
+use advent2020::solver;
+
+use crate::output;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+	Markdown,
+	Html
+}
+
+impl Kind {
+	pub fn from_path(path: &Path) -> Option<Kind> {
+		match path.extension().and_then(|extension| extension.to_str()) {
+			Some("md") | Some("markdown") => return Some(Kind::Markdown),
+			Some("html") | Some("htm") => return Some(Kind::Html),
+			_ => return None
+		}
+	}
+}
+
+struct Line {
+	day: i32,
+	part: i32,
+	name: String,
+	answer: String,
+	expected: String,
+	result: &'static str,
+	time: String
+}
+
+pub fn render(results: &[DayResults], answers: &Answers, kind: Kind) -> String {
+	let lines: Vec<Line> = results.iter().flat_map(|day| day.parts.iter().map(move |part| line(day.day, part, answers))).collect();
+	let failures = failures(results, answers);
+
+	match kind {
+		Kind::Markdown => return markdown(&lines, &failures),
+		Kind::Html => return html(&lines, &failures)
+	}
+}
+
+fn line(day: i32, part: &PartResult, answers: &Answers) -> Line {
+	let expected = answers.expected(day, part.part);
+	let result = match (&part.status, expected) {
+		(Status::Failed(_), _) => "FAILED",
+		(Status::TimedOut(_), _) => "TIMED OUT",
+		(Status::Solved, None) => Verdict::Missing.label(),
+		(Status::Solved, Some(x)) if x == part.answer => Verdict::Pass.label(),
+		(Status::Solved, Some(_)) => Verdict::Fail.label()
+	};
+
+	let name = solver::find(day).map_or_else(|| format!("Day {}", day), |solver| String::from(solver.name()));
+	let time = if part.cached { String::from("cached") } else { output::elapsed_ms(part) };
+	return Line { day, part: part.part, name, answer: part.answer.clone(), expected: expected.map(String::from).unwrap_or_default(), result, time };
+}
+
+// Parts that didn't finish say why; wrong answers say what was expected
+fn failures(results: &[DayResults], answers: &Answers) -> Vec<(String, String)> {
+	let mut failures = vec![];
+	for day in results {
+		for part in &day.parts {
+			let heading = format!("Day {} part {}", day.day, part.part);
+			match (part.status.error(), answers.expected(day.day, part.part)) {
+				(Some(error), _) => failures.push((heading, String::from(error))),
+				(None, Some(expected)) if expected != part.answer => failures.push((heading, format!("got {}, expected {}", part.answer, expected))),
+				_ => ()
+			}
+		}
+	}
+
+	return failures;
+}
+
+fn summary(lines: &[Line]) -> String {
+	let count = |label: &str| lines.iter().filter(|line| line.result == label).count();
+	let failed = lines.len() - count(Verdict::Pass.label()) - count(Verdict::Missing.label());
+	return format!("{} parts: {} passed, {} failed, {} without an expected answer", lines.len(), count(Verdict::Pass.label()), failed, count(Verdict::Missing.label()));
+}
+
+fn source(day: i32) -> String {
+	return format!("src/day{}.rs", day);
+}
+
+fn markdown(lines: &[Line], failures: &[(String, String)]) -> String {
+	let mut text = format!("# Advent of Code 2020\n\n{}\n\n", summary(lines));
+	text += "| Day | Part | Answer | Expected | Result | Time (ms) |\n";
+	text += "| --- | ---: | --- | --- | --- | ---: |\n";
+	for line in lines {
+		let day = format!("[Day {}: {}]({})", line.day, markdown_escape(&line.name), source(line.day));
+		let cells = [day, line.part.to_string(), markdown_escape(&line.answer), markdown_escape(&line.expected), String::from(line.result), line.time.clone()];
+		text += &format!("| {} |\n", cells.join(" | "));
+	}
+
+	if !failures.is_empty() {
+		text += "\n## Failures\n\n";
+		for (heading, detail) in failures {
+			text += &format!("- **{}**: {}\n", heading, markdown_escape(detail));
+		}
+	}
+
+	return text;
+}
+
+// Answers and errors are arbitrary text, so anything that could start formatting or end a table cell is escaped
+fn markdown_escape(value: &str) -> String {
+	let mut escaped = String::new();
+	for c in value.chars() {
+		match c {
+			'\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' => { escaped.push('\\'); escaped.push(c); },
+			'\n' | '\r' => escaped.push(' '),
+			c => escaped.push(c)
+		}
+	}
+
+	return escaped;
+}
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+tr.pass td.result { color: #1a7f37; }
+tr.fail td.result { color: #cf222e; font-weight: bold; }
+tr.missing td.result { color: #9a6700; }";
+
+fn html(lines: &[Line], failures: &[(String, String)]) -> String {
+	let mut page = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code 2020</title>\n");
+	page += &format!("<style>\n{}\n</style>\n</head>\n<body>\n", STYLE);
+	page += &format!("<h1>Advent of Code 2020</h1>\n<p>{}</p>\n", summary(lines));
+	page += "<table>\n<tr><th>Day</th><th>Part</th><th>Answer</th><th>Expected</th><th>Result</th><th>Time (ms)</th></tr>\n";
+	for line in lines {
+		let class = match line.result {
+			"PASS" => "pass",
+			"MISSING" => "missing",
+			_ => "fail"
+		};
+		page += &format!("<tr class=\"{}\"><td><a href=\"{}\">Day {}: {}</a></td><td class=\"number\">{}</td><td>{}</td><td>{}</td><td class=\"result\">{}</td><td class=\"number\">{}</td></tr>\n",
+			class, source(line.day), line.day, html_escape(&line.name), line.part, html_escape(&line.answer), html_escape(&line.expected), line.result, line.time);
+	}
+	page += "</table>\n";
+
+	if !failures.is_empty() {
+		page += "<h2>Failures</h2>\n<ul>\n";
+		for (heading, detail) in failures {
+			page += &format!("<li><strong>{}</strong>: {}</li>\n", heading, html_escape(detail));
+		}
+		page += "</ul>\n";
+	}
+
+	return page + "</body>\n</html>\n";
+}
+
+fn html_escape(value: &str) -> String {
+	return value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;");
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::path::PathBuf;
+	use std::time::Duration;
+
+	fn results() -> Vec<DayResults> {
+		let part = |part, answer: &str, status| PartResult { part, answer: String::from(answer), elapsed: Duration::from_micros(1500), status, cached: false };
+		return vec![
+			DayResults { day: 1, parts: vec![part(1, "514579", Status::Solved), part(2, "5", Status::Solved)] },
+			DayResults { day: 8, parts: vec![part(1, "", Status::Failed(String::from("day 8, line 2: unknown operation 'a|b'")))] }
+		];
+	}
+
+	fn answers() -> Answers {
+		return Answers::parse("[[answer]]\nday = 1\npart = 1\nexpected = \"514579\"\n[[answer]]\nday = 1\npart = 2\nexpected = \"6\"").unwrap();
+	}
+
+	#[test]
+	fn kinds_come_from_the_extension() {
+		assert_eq!(Kind::from_path(&PathBuf::from("run.md")), Some(Kind::Markdown));
+		assert_eq!(Kind::from_path(&PathBuf::from("reports/run.html")), Some(Kind::Html));
+		assert_eq!(Kind::from_path(&PathBuf::from("run.txt")), None);
+	}
+
+	#[test]
+	fn markdown_reports_work() {
+		assert_eq!(render(&results(), &answers(), Kind::Markdown), "# Advent of Code 2020
+
+3 parts: 1 passed, 2 failed, 0 without an expected answer
+
+| Day | Part | Answer | Expected | Result | Time (ms) |
+| --- | ---: | --- | --- | --- | ---: |
+| [Day 1: Report Repair](src/day1.rs) | 1 | 514579 | 514579 | PASS | 1.500 |
+| [Day 1: Report Repair](src/day1.rs) | 2 | 5 | 6 | FAIL | 1.500 |
+| [Day 8: Handheld Halting](src/day8.rs) | 1 |  |  | FAILED | 1.500 |
+
+## Failures
+
+- **Day 1 part 2**: got 5, expected 6
+- **Day 8 part 1**: day 8, line 2: unknown operation 'a\\|b'
+");
+	}
+
+	#[test]
+	fn html_reports_work() {
+		let page = render(&results(), &Answers::default(), Kind::Html);
+		assert!(page.starts_with("<!DOCTYPE html>\n"));
+		assert!(page.contains("<p>3 parts: 0 passed, 1 failed, 2 without an expected answer</p>"));
+		assert!(page.contains("<tr class=\"missing\"><td><a href=\"src/day1.rs\">Day 1: Report Repair</a></td><td class=\"number\">1</td><td>514579</td><td></td><td class=\"result\">MISSING</td><td class=\"number\">1.500</td></tr>"));
+		assert!(page.contains("<li><strong>Day 8 part 1</strong>: day 8, line 2: unknown operation 'a|b'</li>"));
+		assert!(page.ends_with("</html>\n"));
+	}
+
+	#[test]
+	fn html_is_escaped() {
+		assert_eq!(html_escape("<a href=\"x\">&</a>"), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+	}
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+// The runner reports a panic as a failure whose message starts with this, after any day prefix
+pub const PANICKED: &str = "panicked: ";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+	Solved,
+	Failed(String),
+	TimedOut(String)
+}
+
+impl Status {
+	pub fn label(&self) -> &'static str {
+		match self {
+			Status::Solved => return "ok",
+			Status::Failed(_) => return "failed",
+			Status::TimedOut(_) => return "timeout"
+		}
+	}
+
+	pub fn error(&self) -> Option<&str> {
+		match self {
+			Status::Failed(message) | Status::TimedOut(message) => return Some(message),
+			_ => return None
+		}
+	}
+
+	pub fn panicked(&self) -> bool {
+		match self {
+			Status::Failed(message) => return message.starts_with(PANICKED) || message.contains(&format!(": {}", PANICKED)),
+			_ => return false
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct PartResult {
+	pub part: i32,
+	pub answer: String,
+	pub elapsed: Duration,
+	pub status: Status,
+	pub cached: bool
+}
+
+#[derive(Clone, Debug)]
+pub struct DayResults {
+	pub day: i32,
+	pub parts: Vec<PartResult>
+}
\ No newline at end of file
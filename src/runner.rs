@@ -0,0 +1,341 @@
+use std::{
+	any::Any,
+	future::Future,
+	io,
+	panic::{self, AssertUnwindSafe},
+	sync::Arc,
+	time::{Duration, Instant}
+};
+
+use futures::future::join_all;
+use tokio::{sync::Semaphore, task, time};
+
+use crate::cache::Cache;
+use crate::cancel::{self, Token};
+use crate::error::{Error, Result};
+use crate::input::{Input, Source};
+use crate::progress::{self, Board};
+use crate::results::{DayResults, PartResult, Status, PANICKED};
+use crate::solver::Solver;
+use crate::visualize::{self, Target};
+
+const DRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+// Solvers are pure CPU work, so each day runs on the blocking pool rather than an async worker, and the semaphore
+// keeps at most `jobs` of them running at once so their timings aren't skewed by fighting over cores
+pub async fn run_days(days: Vec<(&'static dyn Solver, Source)>, parts: &[i32], jobs: usize, timeout: Option<Duration>, cache: Option<Cache>, visualize: Option<Target>, progress: bool) -> Vec<DayResults> {
+	let permits = Arc::new(Semaphore::new(jobs.max(1)));
+	let board = if progress { Some(Board::new()) } else { None };
+	let day_futures = days.into_iter().map(|(solver, source)| {
+		let permits = permits.clone();
+		let parts = parts.to_vec();
+		let cache = cache.clone();
+		let visualize = visualize.clone();
+		let board = board.clone();
+		async move {
+			let permit = permits.acquire_owned().await;
+			let token = Token::new();
+			let day_token = token.clone();
+			let handle = task::spawn_blocking(move || {
+				let results = cancel::with_token(&day_token, || visualize::with_target(visualize.as_ref(), || progress::with_board(board.as_ref(), || match &cache {
+					Some(cache) => run_day_cached(solver, &parts, &source, cache),
+					None => run_day(solver, &parts, &source)
+				})));
+				drop(permit);
+				return results;
+			});
+
+			// A timed out day is reported straight away, but its thread keeps the permit until it winds down at the next
+			// cancellation check, so the next day doesn't start while it's still using a core
+			match timeout {
+				Some(limit) => match time::timeout(limit, handle).await {
+					Ok(result) => return (solver.day(), Some(result)),
+					Err(_) => {
+						token.cancel();
+						return (solver.day(), None);
+					}
+				},
+				None => return (solver.day(), Some(handle.await))
+			}
+		}
+	});
+
+	// Parts catch their own panics, so a join error means the task itself went away; the day still gets reported
+	let finished = match &board {
+		Some(board) => with_progress(board, join_all(day_futures)).await,
+		None => join_all(day_futures).await
+	};
+	let mut results: Vec<DayResults> = finished.into_iter().map(|(day, result)| match result {
+		Some(Ok(day_results)) => day_results,
+		Some(Err(e)) if e.is_panic() => failed_day(day, parts, &format!("{}{}", PANICKED, panic_message(e.into_panic().as_ref()))),
+		Some(Err(e)) => failed_day(day, parts, &e.to_string()),
+		None => timed_out_day(day, parts, timeout.unwrap_or_default())
+	}).collect();
+	results.sort_by_key(|result| result.day);
+
+	return results;
+}
+
+// Redraws the running parts on stderr until `work` is done, then wipes them so the results start on a clean line
+async fn with_progress<F>(board: &Board, work: F) -> F::Output where F: Future {
+	tokio::pin!(work);
+	let mut drawn = 0;
+	loop {
+		tokio::select! {
+			output = &mut work => {
+				let _ = progress::redraw(&mut io::stderr(), &[], drawn);
+				return output;
+			},
+			_ = time::sleep(DRAW_INTERVAL) => {
+				let lines = progress::render(&board.active(), Instant::now());
+				drawn = progress::redraw(&mut io::stderr(), &lines, drawn).unwrap_or(drawn);
+			}
+		}
+	}
+}
+
+pub fn run_day(solver: &dyn Solver, parts: &[i32], source: &Source) -> DayResults {
+	return solve(solver, parts, Input::load(solver.input_kind(), source));
+}
+
+// For input that arrived some other way than a file, such as in a request body
+pub fn run_text(solver: &dyn Solver, parts: &[i32], text: String) -> DayResults {
+	return solve(solver, parts, Input::parse(solver.input_kind(), text));
+}
+
+// Only the parts without a cached answer for this exact input and solver version get solved, and only answers
+// that were actually solved get cached
+pub fn run_day_cached(solver: &dyn Solver, parts: &[i32], source: &Source, cache: &Cache) -> DayResults {
+	let text = match source.read() {
+		Ok(text) => text,
+		Err(e) => return solve(solver, parts, Err(e))
+	};
+	let key = Cache::key(solver.version(), &text);
+
+	let cached = |part: i32| cache.get(solver.day(), part, &key).map(|(answer, elapsed)| PartResult { part, answer, elapsed, status: Status::Solved, cached: true });
+	let mut found: Vec<PartResult> = parts.iter().filter_map(|part| cached(*part)).collect();
+	let missing: Vec<i32> = parts.iter().copied().filter(|part| !found.iter().any(|result| result.part == *part)).collect();
+
+	if !missing.is_empty() {
+		let solved = solve(solver, &missing, Input::parse(solver.input_kind(), text));
+		for result in solved.parts.iter().filter(|result| result.status == Status::Solved) {
+			// A cache that can't be written just means the next run solves it again
+			let _ = cache.put(solver.day(), result.part, &key, &result.answer, result.elapsed);
+		}
+		found.extend(solved.parts);
+	}
+
+	found.sort_by_key(|result| parts.iter().position(|part| *part == result.part));
+	return DayResults { day: solver.day(), parts: found };
+}
+
+// Each part runs under catch_unwind so a panic in part 1 still leaves part 2 with a chance to report
+fn solve(solver: &dyn Solver, parts: &[i32], data: Result<Input>) -> DayResults {
+	let run_part = |part| {
+		let start = Instant::now();
+		let result = data.as_ref().map_err(|e| e.clone()).and_then(|data| run_part(solver, part, data));
+		let elapsed = start.elapsed();
+
+		match result {
+			Ok(answer) => return PartResult { part, answer, elapsed, status: Status::Solved, cached: false },
+			Err(e) => return PartResult { part, answer: String::new(), elapsed, status: Status::Failed(e.with_day(solver.day()).to_string()), cached: false }
+		}
+	};
+
+	return DayResults { day: solver.day(), parts: parts.iter().map(|part| run_part(*part)).collect() };
+}
+
+fn run_part(solver: &dyn Solver, part: i32, data: &Input) -> Result<String> {
+	match panic::catch_unwind(AssertUnwindSafe(|| progress::for_part(solver.day(), part, || visualize::for_part(solver.day(), part, || solver.part(part, data))))) {
+		Ok(result) => return result,
+		Err(payload) => return Err(Error::new(format!("{}{}", PANICKED, panic_message(payload.as_ref()))))
+	}
+}
+
+// For when a day's task died without handing back any results at all
+pub fn failed_day(day: i32, parts: &[i32], message: &str) -> DayResults {
+	let failed = |part| PartResult { part, answer: String::new(), elapsed: Duration::default(), status: Status::Failed(Error::new(message).with_day(day).to_string()), cached: false };
+	return DayResults { day, parts: parts.iter().map(|part| failed(*part)).collect() };
+}
+
+pub fn timed_out_day(day: i32, parts: &[i32], limit: Duration) -> DayResults {
+	let message = Error::new(format!("no answer within {:?}", limit)).with_day(day).to_string();
+	let timed_out = |part| PartResult { part, answer: String::new(), elapsed: limit, status: Status::TimedOut(message.clone()), cached: false };
+	return DayResults { day, parts: parts.iter().map(|part| timed_out(*part)).collect() };
+}
+
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+	if let Some(message) = payload.downcast_ref::<&str>() { return String::from(*message); }
+	if let Some(message) = payload.downcast_ref::<String>() { return message.clone(); }
+	return String::from("unknown panic");
+}
+
+pub fn any_failed(results: &[DayResults]) -> bool {
+	return results.iter().flat_map(|day| day.parts.iter()).any(|part| part.status != Status::Solved);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use crate::input::InputKind;
+
+	struct Grumpy;
+
+	impl Solver for Grumpy {
+		fn day(&self) -> i32 { 99 }
+		fn name(&self) -> &str { "Grumpy" }
+		fn input_kind(&self) -> InputKind { InputKind::Chunk }
+		fn version(&self) -> &str { "1" }
+		fn part1(&self, _: &Input) -> Result<String> { panic!("not today") }
+		fn part2(&self, input: &Input) -> Result<String> { Ok(input.chunk().len().to_string()) }
+	}
+
+	// Tests run in parallel, so each one that counts sleepers gets counters of its own
+	struct Counters {
+		running: AtomicUsize,
+		most_running: AtomicUsize
+	}
+
+	impl Counters {
+		const fn new() -> Counters {
+			return Counters { running: AtomicUsize::new(0), most_running: AtomicUsize::new(0) };
+		}
+	}
+
+	struct Sleepy(i32, &'static Counters);
+
+	impl Solver for Sleepy {
+		fn day(&self) -> i32 { self.0 }
+		fn name(&self) -> &str { "Sleepy" }
+		fn input_kind(&self) -> InputKind { InputKind::Chunk }
+		fn version(&self) -> &str { "1" }
+		fn part1(&self, _: &Input) -> Result<String> {
+			let running = self.1.running.fetch_add(1, Ordering::SeqCst) + 1;
+			self.1.most_running.fetch_max(running, Ordering::SeqCst);
+			std::thread::sleep(Duration::from_millis(20));
+			self.1.running.fetch_sub(1, Ordering::SeqCst);
+			Ok(self.0.to_string())
+		}
+		fn part2(&self, _: &Input) -> Result<String> { Ok(String::new()) }
+	}
+
+	static JOBS_COUNTERS: Counters = Counters::new();
+	static SLEEPIES: [Sleepy; 4] = [Sleepy(4, &JOBS_COUNTERS), Sleepy(3, &JOBS_COUNTERS), Sleepy(2, &JOBS_COUNTERS), Sleepy(1, &JOBS_COUNTERS)];
+
+	#[tokio::test]
+	async fn jobs_limit_how_many_days_run_at_once() {
+		let days = SLEEPIES.iter().map(|solver| (solver as &'static dyn Solver, Source::parse("data/day15.txt"))).collect();
+		let results = run_days(days, &[1], 1, None, None, None, false).await;
+
+		assert_eq!(results.iter().map(|result| result.day).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+		assert_eq!(results[2].parts[0].answer, "3");
+		assert_eq!(JOBS_COUNTERS.most_running.load(Ordering::SeqCst), 1);
+	}
+
+	struct Stuck;
+
+	impl Solver for Stuck {
+		fn day(&self) -> i32 { 98 }
+		fn name(&self) -> &str { "Stuck" }
+		fn input_kind(&self) -> InputKind { InputKind::Chunk }
+		fn version(&self) -> &str { "1" }
+		fn part1(&self, _: &Input) -> Result<String> {
+			loop {
+				cancel::check()?;
+				std::thread::sleep(Duration::from_millis(1));
+			}
+		}
+		fn part2(&self, _: &Input) -> Result<String> { Ok(String::new()) }
+	}
+
+	static TIMEOUT_COUNTERS: Counters = Counters::new();
+	static UNSTUCK: Sleepy = Sleepy(4, &TIMEOUT_COUNTERS);
+
+	#[tokio::test]
+	async fn stuck_days_time_out_without_holding_up_the_rest() {
+		let days: Vec<(&'static dyn Solver, Source)> = vec![(&Stuck, Source::parse("data/day15.txt")), (&UNSTUCK, Source::parse("data/day15.txt"))];
+		let results = run_days(days, &[1], 1, Some(Duration::from_millis(100)), None, None, false).await;
+
+		assert_eq!(results[0].parts[0].answer, "4");
+		assert_eq!(results[1].parts[0].status, Status::TimedOut(String::from("day 98: no answer within 100ms")));
+		assert!(any_failed(&results));
+	}
+
+	static OVERTIME_COUNTERS: Counters = Counters::new();
+	static OVERTIME: [Sleepy; 2] = [Sleepy(1, &OVERTIME_COUNTERS), Sleepy(2, &OVERTIME_COUNTERS)];
+
+	// Sleepy never checks for cancellation, so both days run well past the timeout
+	#[tokio::test]
+	async fn timed_out_days_keep_their_job_until_they_stop() {
+		let days = OVERTIME.iter().map(|solver| (solver as &'static dyn Solver, Source::parse("data/day15.txt"))).collect();
+		let results = run_days(days, &[1], 1, Some(Duration::from_millis(5)), None, None, false).await;
+
+		assert!(results.iter().all(|result| result.parts[0].status == Status::TimedOut(format!("day {}: no answer within 5ms", result.day))));
+		tokio::time::sleep(Duration::from_millis(60)).await;
+		assert_eq!(OVERTIME_COUNTERS.most_running.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn panics_are_reported_per_part() {
+		let results = run_day(&Grumpy, &[1, 2], &Source::parse("data/day15.txt"));
+		assert_eq!(results.parts[0].status, Status::Failed(String::from("day 99: panicked: not today")));
+		assert!(results.parts[0].status.panicked());
+		assert_eq!(results.parts[1].status, Status::Solved);
+		assert!(any_failed(&[results]));
+	}
+
+	#[test]
+	fn text_is_parsed_like_a_file() {
+		let results = run_text(&Grumpy, &[2], String::from("abc\r\n"));
+		assert_eq!(results.parts[0].answer, "3");
+	}
+
+	#[test]
+	fn missing_input_fails_every_part() {
+		let results = run_day(&Grumpy, &[1, 2], &Source::parse("data/nope.txt"));
+		assert!(results.parts.iter().all(|part| part.status.error().unwrap().starts_with("day 99: couldn't read data/nope.txt")));
+	}
+
+	static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+	struct Counted;
+
+	impl Solver for Counted {
+		fn day(&self) -> i32 { 97 }
+		fn name(&self) -> &str { "Counted" }
+		fn input_kind(&self) -> InputKind { InputKind::Chunk }
+		fn version(&self) -> &str { "1" }
+		fn part1(&self, input: &Input) -> Result<String> {
+			CALLS.fetch_add(1, Ordering::SeqCst);
+			Ok(input.chunk().len().to_string())
+		}
+		fn part2(&self, _: &Input) -> Result<String> { Err(Error::new("never")) }
+	}
+
+	#[test]
+	fn cached_answers_are_reused_and_marked() {
+		let dir = std::env::temp_dir().join(format!("advent2020-runner-cache-{}", std::process::id()));
+		let cache = Cache::new(&dir);
+		let source = Source::parse("data/day15.txt");
+
+		let first = run_day_cached(&Counted, &[1, 2], &source, &cache);
+		let second = run_day_cached(&Counted, &[2, 1], &source, &cache);
+		assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+		assert!(!first.parts[0].cached);
+		assert_eq!(second.parts.iter().map(|part| part.part).collect::<Vec<_>>(), vec![2, 1]);
+		assert_eq!(second.parts[1].answer, first.parts[0].answer);
+		assert!(second.parts[1].cached);
+		assert!(!second.parts[0].cached && second.parts[0].status != Status::Solved);
+
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+
+	#[test]
+	fn failed_days_cover_every_part() {
+		let results = failed_day(3, &[1, 2], "task was cancelled");
+		assert_eq!(results.parts.len(), 2);
+		assert_eq!(results.parts[1].status, Status::Failed(String::from("day 3: task was cancelled")));
+	}
+}
\ No newline at end of file
@@ -0,0 +1,171 @@
+use std::{
+	fs,
+	path::{Path, PathBuf}
+};
+
+use advent2020::input::InputKind;
+
+// The identifier register! expects, the type part1/part2 take, and how the example test turns text into that type
+fn kind_parts(kind: InputKind) -> (&'static str, &'static str, &'static str, &'static str) {
+	match kind {
+		InputKind::Lines => return ("Lines", "&[String]", "read_lines", "&read_lines(data)"),
+		InputKind::Chunk => return ("Chunk", "&str", "read_chunk", "&read_chunk(data)"),
+		InputKind::Numbers => return ("Numbers", "&[i32]", "read_lines_to_numbers", "&read_lines_to_numbers(data).unwrap()"),
+		InputKind::Records => return ("Records", "&[Vec<String>]", "read_records", "&read_records(data)")
+	}
+}
+
+fn module_source(kind: InputKind) -> String {
+	let (_, data_type, reader, conversion) = kind_parts(kind);
+
+	return format!("use crate::error::{{Error, Result}};
+
+pub const VERSION: &str = \"1\";
+
+pub fn part1(_data: {data_type}) -> Result<i64> {{
+	return Err(Error::new(\"part 1 isn't solved yet\"));
+}}
+
+pub fn part2(_data: {data_type}) -> Result<i64> {{
+	return Err(Error::new(\"part 2 isn't solved yet\"));
+}}
+
+#[cfg(test)]
+mod tests {{
+	use super::*;
+	use crate::input::{reader};
+
+	// Paste the puzzle's example in here, fill in its answer and drop the #[ignore]
+	#[test]
+	#[ignore]
+	fn part1_works() {{
+		let data = \"\";
+
+		assert_eq!(part1({conversion}).unwrap(), 0);
+	}}
+}}", data_type = data_type, reader = reader, conversion = conversion);
+}
+
+fn read(path: &Path) -> Result<String, String> {
+	return fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e));
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+	return fs::write(path, contents).map_err(|e| format!("couldn't write {}: {}", path.display(), e));
+}
+
+fn day_of(module: &str) -> i32 {
+	return module.trim().trim_start_matches("pub mod ").trim_start_matches("day").trim_end_matches(';').parse().unwrap_or(0);
+}
+
+// Adds `pub mod dayN;` among the other day modules, keeping them in day order
+fn add_module(lib: &str, day: i32) -> Result<String, String> {
+	let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+	let days: Vec<usize> = lines.iter().enumerate().filter(|(_, line)| line.starts_with("pub mod day")).map(|(index, _)| index).collect();
+	let last = *days.last().ok_or_else(|| String::from("couldn't find the day modules in src/lib.rs"))?;
+
+	let position = days.iter().copied().find(|index| day_of(&lines[*index]) > day).unwrap_or(last + 1);
+	lines.insert(position, format!("pub mod day{};", day));
+
+	return Ok(lines.join("\n"));
+}
+
+// Adds dayN to the module imports and a register! entry to DAYS, both in day order
+fn add_registration(solver: &str, day: i32, name: &str, kind: InputKind) -> Result<String, String> {
+	let mut lines: Vec<String> = solver.lines().map(String::from).collect();
+
+	let imports = lines.iter().position(|line| line.starts_with("use crate::{day")).ok_or_else(|| String::from("couldn't find the day imports in src/solver.rs"))?;
+	let mut modules: Vec<String> = lines[imports].trim_start_matches("use crate::{").trim_end_matches("};").split(", ").map(String::from).collect();
+	modules.push(format!("day{}", day));
+	modules.sort_by_key(|module| day_of(module));
+	lines[imports] = format!("use crate::{{{}}};", modules.join(", "));
+
+	let entries: Vec<usize> = lines.iter().enumerate().filter(|(_, line)| line.trim_start().starts_with("register!(day")).map(|(index, _)| index).collect();
+	let last = *entries.last().ok_or_else(|| String::from("couldn't find the registered days in src/solver.rs"))?;
+	let entry_day = |line: &str| line.trim_start().trim_start_matches("register!(").split(',').next().map_or(0, day_of);
+	let position = entries.iter().copied().find(|index| entry_day(&lines[*index]) > day).unwrap_or(last + 1);
+	lines.insert(position, format!("\tregister!(day{}, {}, \"{}\", {}),", day, day, name.replace('"', "\\\""), kind_parts(kind).0));
+
+	return Ok(lines.join("\n"));
+}
+
+// Returns every file it touched; nothing is written until all the edits have been worked out
+pub fn new_day(root: &Path, day: i32, name: &str, kind: InputKind) -> Result<Vec<PathBuf>, String> {
+	let module = root.join(format!("src/day{}.rs", day));
+	let data = root.join(format!("data/day{}.txt", day));
+	let lib = root.join("src/lib.rs");
+	let solver = root.join("src/solver.rs");
+
+	if module.exists() {
+		return Err(format!("{} already exists", module.display()));
+	}
+
+	let lib_source = add_module(&read(&lib)?, day)?;
+	let solver_source = add_registration(&read(&solver)?, day, name, kind)?;
+
+	write(&module, &module_source(kind))?;
+	write(&lib, &lib_source)?;
+	write(&solver, &solver_source)?;
+	let mut touched = vec![module, lib, solver];
+
+	// A real input may already be sitting there, and it shouldn't be clobbered
+	if !data.exists() {
+		write(&data, "")?;
+		touched.push(data);
+	}
+
+	return Ok(touched);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// A cut-down crate with two days, so the test doesn't care which days the real one has
+	const LIB: &str = "pub mod error;\npub mod solver;\n\npub mod day1;\npub mod day2;";
+	const SOLVER: &str = "use crate::{day1, day2};\n\nstatic DAYS: &[Day] = &[\n\tregister!(day1, 1, \"Report Repair\", Numbers),\n\tregister!(day2, 2, \"Password Philosophy\", Lines),\n];";
+
+	fn scratch_tree(label: &str) -> PathBuf {
+		let root = std::env::temp_dir().join(format!("advent2020-scaffold-{}-{}", label, std::process::id()));
+		let _ = fs::remove_dir_all(&root);
+		fs::create_dir_all(root.join("src")).unwrap();
+		fs::create_dir_all(root.join("data")).unwrap();
+		fs::write(root.join("src/lib.rs"), LIB).unwrap();
+		fs::write(root.join("src/solver.rs"), SOLVER).unwrap();
+
+		return root;
+	}
+
+	#[test]
+	fn new_days_are_registered_in_order() {
+		let root = scratch_tree("register");
+		let touched = new_day(&root, 3, "Toboggan Trajectory", InputKind::Records).unwrap();
+		assert_eq!(touched.len(), 4);
+
+		let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+		assert_eq!(lib, "pub mod error;\npub mod solver;\n\npub mod day1;\npub mod day2;\npub mod day3;");
+
+		let solver = fs::read_to_string(root.join("src/solver.rs")).unwrap();
+		assert!(solver.starts_with("use crate::{day1, day2, day3};"));
+		assert!(solver.ends_with("\tregister!(day2, 2, \"Password Philosophy\", Lines),\n\tregister!(day3, 3, \"Toboggan Trajectory\", Records),\n];"));
+
+		let module = fs::read_to_string(root.join("src/day3.rs")).unwrap();
+		assert!(module.contains("pub const VERSION: &str = \"1\";"));
+		assert!(module.contains("pub fn part1(_data: &[Vec<String>]) -> Result<i64> {"));
+		assert!(module.contains("assert_eq!(part1(&read_records(data)).unwrap(), 0);"));
+		assert_eq!(fs::read_to_string(root.join("data/day3.txt")).unwrap(), "");
+
+		assert!(new_day(&root, 3, "Again", InputKind::Lines).unwrap_err().ends_with("day3.rs already exists"));
+		fs::remove_dir_all(&root).unwrap();
+	}
+
+	#[test]
+	fn gaps_are_filled_in_day_order() {
+		assert_eq!(add_module("mod a;\npub mod day1;\npub mod day3;", 2).unwrap(), "mod a;\npub mod day1;\npub mod day2;\npub mod day3;");
+		assert!(add_module("mod a;", 2).is_err());
+
+		let solver = "use crate::{day1, day3};\nstatic DAYS: &[Day] = &[\n\tregister!(day1, 1, \"A\", Lines),\n\tregister!(day3, 3, \"C\", Lines),\n];";
+		assert_eq!(add_registration(solver, 2, "B", InputKind::Chunk).unwrap(),
+			"use crate::{day1, day2, day3};\nstatic DAYS: &[Day] = &[\n\tregister!(day1, 1, \"A\", Lines),\n\tregister!(day2, 2, \"B\", Chunk),\n\tregister!(day3, 3, \"C\", Lines),\n];");
+	}
+}
\ No newline at end of file
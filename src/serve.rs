@@ -0,0 +1,344 @@
+use std::{
+	io,
+	sync::Arc,
+	time::Duration
+};
+
+use tokio::{
+	io::{AsyncReadExt, AsyncWriteExt},
+	net::{TcpListener, TcpStream},
+	sync::Semaphore,
+	task,
+	time
+};
+
+use advent2020::cancel::{self, Token};
+use advent2020::results::{DayResults, Status};
+use advent2020::runner;
+use advent2020::solver;
+
+use crate::output;
+
+// Puzzle inputs are tens of kilobytes, so anything near these limits isn't one
+const MAX_HEAD: usize = 16 * 1024;
+const MAX_BODY: usize = 8 * 1024 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Shared by every connection. The permits keep at most --jobs solvers running at once, the same as the CLI runner,
+// and a client gets `read_timeout` to send its whole request
+pub struct Limits {
+	pub timeout: Option<Duration>,
+	pub read_timeout: Duration,
+	permits: Arc<Semaphore>
+}
+
+impl Limits {
+	pub fn new(timeout: Option<Duration>, jobs: usize) -> Limits {
+		return Limits { timeout, read_timeout: READ_TIMEOUT, permits: Arc::new(Semaphore::new(jobs.max(1))) };
+	}
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+	pub method: String,
+	pub path: String,
+	pub body: Vec<u8>
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+	pub status: u16,
+	pub body: String
+}
+
+impl Response {
+	fn json(status: u16, body: String) -> Response {
+		return Response { status, body: body + "\n" };
+	}
+
+	fn error(status: u16, message: &str) -> Response {
+		return Response::json(status, format!("{{\"error\": {}}}", output::json_string(message)));
+	}
+
+	fn to_http(&self) -> String {
+		return format!("HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+			self.status, reason(self.status), self.body.len(), self.body);
+	}
+}
+
+fn reason(status: u16) -> &'static str {
+	match status {
+		200 => return "OK",
+		400 => return "Bad Request",
+		404 => return "Not Found",
+		405 => return "Method Not Allowed",
+		408 => return "Request Timeout",
+		411 => return "Length Required",
+		413 => return "Payload Too Large",
+		422 => return "Unprocessable Entity",
+		431 => return "Request Header Fields Too Large",
+		504 => return "Gateway Timeout",
+		_ => return "Internal Server Error"
+	}
+}
+
+// One request per connection, answered and closed, which is all a client posting puzzle inputs needs
+pub async fn serve(listener: TcpListener, limits: Limits) {
+	let limits = Arc::new(limits);
+	loop {
+		match listener.accept().await {
+			Ok((stream, _)) => { tokio::spawn(handle(stream, limits.clone())); },
+			Err(e) => eprintln!("couldn't accept a connection: {}", e)
+		}
+	}
+}
+
+async fn handle(mut stream: TcpStream, limits: Arc<Limits>) {
+	let response = match time::timeout(limits.read_timeout, read_request(&mut stream)).await {
+		Ok(Ok(Ok(request))) => respond(request, &limits).await,
+		Ok(Ok(Err(response))) => response,
+		Ok(Err(_)) => return,
+		Err(_) => Response::error(408, "the request took too long to arrive")
+	};
+
+	// The client may already have gone away, and there's no one left to tell
+	let _ = stream.write_all(response.to_http().as_bytes()).await;
+	let _ = stream.shutdown(std::net::Shutdown::Write);
+}
+
+// The outer error is the connection failing; the inner one is a request that can't be served, with its reply
+async fn read_request(stream: &mut TcpStream) -> io::Result<Result<Request, Response>> {
+	let mut buffer = vec![];
+	let mut chunk = [0u8; 4096];
+
+	let head_end = loop {
+		if let Some(index) = buffer.windows(4).position(|window| window == b"\r\n\r\n") { break index; }
+		if buffer.len() > MAX_HEAD { return Ok(Err(Response::error(431, "the request headers are too long"))); }
+
+		let read = stream.read(&mut chunk).await?;
+		if read == 0 { return Ok(Err(Response::error(400, "the request ended before its headers did"))); }
+		buffer.extend_from_slice(&chunk[..read]);
+	};
+
+	let (method, path, length) = match parse_head(&String::from_utf8_lossy(&buffer[..head_end])) {
+		Ok(head) => head,
+		Err(response) => return Ok(Err(response))
+	};
+
+	let mut body = buffer.split_off(head_end + 4);
+	while body.len() < length {
+		let read = stream.read(&mut chunk).await?;
+		if read == 0 { return Ok(Err(Response::error(400, "the request body is shorter than its Content-Length"))); }
+		body.extend_from_slice(&chunk[..read]);
+	}
+	body.truncate(length);
+
+	return Ok(Ok(Request { method, path, body }));
+}
+
+// Only Content-Length bodies are understood; chunked uploads get asked for a length instead
+pub fn parse_head(head: &str) -> Result<(String, String, usize), Response> {
+	let mut lines = head.split("\r\n");
+	let request_line: Vec<&str> = lines.next().unwrap_or("").split(' ').collect();
+	if request_line.len() != 3 || !request_line[2].starts_with("HTTP/") {
+		return Err(Response::error(400, "malformed request line"));
+	}
+
+	let mut length = None;
+	for line in lines {
+		let (name, value) = line.split_once(':').ok_or_else(|| Response::error(400, "malformed header"))?;
+		if name.trim().eq_ignore_ascii_case("content-length") {
+			length = Some(value.trim().parse::<usize>().map_err(|_| Response::error(400, "Content-Length isn't a number"))?);
+		}
+	}
+
+	let method = String::from(request_line[0]);
+	let length = match length {
+		Some(length) if length > MAX_BODY => return Err(Response::error(413, "the request body is too large")),
+		Some(length) => length,
+		None if method == "POST" => return Err(Response::error(411, "POST requests need a Content-Length")),
+		None => 0
+	};
+
+	return Ok((method, String::from(request_line[1]), length));
+}
+
+pub async fn respond(request: Request, limits: &Limits) -> Response {
+	let path = request.path.split('?').next().unwrap_or("");
+	let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+	match (request.method.as_str(), segments.as_slice()) {
+		("GET", ["days"]) => return list_days(),
+		("POST", ["day", day, "part", part]) => return solve(day, part, request.body, limits).await,
+		(_, ["days"]) => return Response::error(405, "use GET /days"),
+		(_, ["day", _, "part", _]) => return Response::error(405, "use POST with the puzzle input as the body"),
+		_ => return Response::error(404, "try POST /day/{n}/part/{p} or GET /days")
+	}
+}
+
+fn list_days() -> Response {
+	let days: Vec<String> = solver::solvers().iter().map(|solver| format!("{{\"day\": {}, \"name\": {}}}", solver.day(), output::json_string(solver.name()))).collect();
+	return Response::json(200, format!("[{}]", days.join(", ")));
+}
+
+async fn solve(day: &str, part: &str, body: Vec<u8>, limits: &Limits) -> Response {
+	let solver = match day.parse::<i32>().ok().and_then(solver::find) {
+		Some(solver) => solver,
+		None => return Response::error(404, &format!("there's no solver for day {}", day))
+	};
+	let part = match part {
+		"1" => 1,
+		"2" => 2,
+		_ => return Response::error(404, &format!("day {} has no part {}", day, part))
+	};
+	let text = match String::from_utf8(body) {
+		Ok(text) => text,
+		Err(_) => return Response::error(400, "the puzzle input isn't UTF-8")
+	};
+
+	// The same blocking pool, permits and cancellation the CLI runner uses, so a stuck request doesn't hold an async
+	// worker and a burst of requests waits its turn rather than flooding the pool. The permit goes with the solver's
+	// thread, which keeps running after a timeout until it reaches a cancellation check or finishes
+	let permit = limits.permits.clone().acquire_owned().await;
+	let token = Token::new();
+	let day_token = token.clone();
+	let handle = task::spawn_blocking(move || {
+		let results = cancel::with_token(&day_token, || runner::run_text(solver, &[part], text));
+		drop(permit);
+		return results;
+	});
+
+	let joined = match limits.timeout {
+		Some(limit) => match time::timeout(limit, handle).await {
+			Ok(joined) => joined,
+			Err(_) => {
+				token.cancel();
+				return result_response(runner::timed_out_day(solver.day(), &[part], limit));
+			}
+		},
+		None => handle.await
+	};
+
+	return result_response(joined.unwrap_or_else(|e| runner::failed_day(solver.day(), &[part], &e.to_string())));
+}
+
+fn result_response(results: DayResults) -> Response {
+	let part = &results.parts[0];
+	let status = match part.status {
+		Status::Solved => 200,
+		Status::Failed(_) => 422,
+		Status::TimedOut(_) => 504
+	};
+
+	return Response::json(status, output::json_object(results.day, part));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::net::SocketAddr;
+
+	async fn start(limits: Limits) -> SocketAddr {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let address = listener.local_addr().unwrap();
+		tokio::spawn(serve(listener, limits));
+		return address;
+	}
+
+	// A bare-bones client, so the test goes over a real socket the way curl would
+	async fn send(address: SocketAddr, request: &str) -> (u16, String) {
+		let mut stream = TcpStream::connect(address).await.unwrap();
+		stream.write_all(request.as_bytes()).await.unwrap();
+
+		let mut response = String::new();
+		stream.read_to_string(&mut response).await.unwrap();
+
+		let (head, body) = response.split_once("\r\n\r\n").unwrap();
+		return (head[9..12].parse().unwrap(), String::from(body));
+	}
+
+	fn post(path: &str, body: &str) -> String {
+		return format!("POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}", path, body.len(), body);
+	}
+
+	const PROGRAM: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+
+	#[tokio::test]
+	async fn answers_come_back_as_json() {
+		let address = start(Limits::new(None, 2)).await;
+
+		let (status, body) = send(address, &post("/day/8/part/2", PROGRAM)).await;
+		assert_eq!(status, 200);
+		assert!(body.starts_with("{\"day\": 8, \"part\": 2, \"answer\": \"8\", \"elapsed_ms\": "));
+		assert!(body.ends_with("\"status\": \"ok\", \"error\": null, \"cached\": false}\n"));
+
+		let (status, body) = send(address, &post("/day/8/part/1", "nop +0\nacc +1\nhcf +4\n")).await;
+		assert_eq!(status, 422);
+		assert!(body.contains("\"error\": \"day 8, line 3: unknown operation 'hcf'\""));
+
+		let (status, body) = send(address, "GET /days HTTP/1.1\r\n\r\n").await;
+		assert_eq!(status, 200);
+		assert!(body.starts_with("[{\"day\": 1, \"name\": \"Report Repair\"}, "));
+	}
+
+	#[tokio::test]
+	async fn bad_requests_are_refused() {
+		let address = start(Limits::new(None, 2)).await;
+
+		assert_eq!(send(address, &post("/day/25/part/1", "")).await, (404, String::from("{\"error\": \"there's no solver for day 25\"}\n")));
+		assert_eq!(send(address, &post("/day/8/part/3", "")).await.0, 404);
+		assert_eq!(send(address, &post("/nowhere", "")).await.0, 404);
+		assert_eq!(send(address, "GET /day/8/part/1 HTTP/1.1\r\n\r\n").await.0, 405);
+		assert_eq!(send(address, "POST /day/8/part/1 HTTP/1.1\r\n\r\n").await.0, 411);
+		assert_eq!(send(address, "POST /day/8/part/1 HTTP/1.1\r\nContent-Length: 999999999\r\n\r\n").await.0, 413);
+		assert_eq!(send(address, "nonsense\r\n\r\n").await.0, 400);
+	}
+
+	#[tokio::test]
+	async fn inputs_that_would_recurse_forever_are_refused() {
+		let address = start(Limits::new(None, 2)).await;
+
+		let (status, body) = send(address, &post("/day/7/part/1", "light red bags contain 1 light red bag.\n")).await;
+		assert_eq!(status, 422);
+		assert!(body.contains("\"error\": \"day 7: light red bags end up containing themselves\""));
+
+		// Still up and answering
+		assert_eq!(send(address, &post("/day/8/part/2", PROGRAM)).await.0, 200);
+	}
+
+	#[tokio::test]
+	async fn slow_answers_time_out() {
+		let address = start(Limits::new(Some(Duration::from_millis(50)), 2)).await;
+
+		let (status, body) = send(address, &post("/day/15/part/2", "0,3,6\n")).await;
+		assert_eq!(status, 504);
+		assert!(body.contains("\"status\": \"timeout\", \"error\": \"day 15: no answer within 50ms\""));
+	}
+
+	#[tokio::test]
+	async fn slow_clients_time_out() {
+		let mut limits = Limits::new(None, 2);
+		limits.read_timeout = Duration::from_millis(50);
+		let address = start(limits).await;
+
+		assert_eq!(send(address, "POST /day/8/part/1 HTTP/1.1\r\nContent-Le").await.0, 408);
+		assert_eq!(send(address, &post("/day/8/part/1", "nop +0\nacc +1\njmp +4")[..60]).await.0, 408);
+	}
+
+	#[tokio::test]
+	async fn requests_beyond_the_jobs_limit_wait_their_turn() {
+		let address = start(Limits::new(None, 1)).await;
+
+		let request = post("/day/8/part/2", PROGRAM);
+		let (first, second) = futures::join!(send(address, &request), send(address, &request));
+		assert_eq!((first.0, second.0), (200, 200));
+	}
+
+	#[test]
+	fn heads_are_parsed() {
+		assert_eq!(parse_head("POST /day/1/part/1 HTTP/1.1\r\ncontent-length: 12"), Ok((String::from("POST"), String::from("/day/1/part/1"), 12)));
+		assert_eq!(parse_head("GET /days HTTP/1.0\r\nHost: x"), Ok((String::from("GET"), String::from("/days"), 0)));
+		assert_eq!(parse_head("POST /day/1/part/1 HTTP/1.1\r\nContent-Length: lots").unwrap_err().status, 400);
+		assert_eq!(parse_head("POST /day/1/part/1 HTTP/1.1\r\nbroken").unwrap_err().status, 400);
+	}
+}
\ No newline at end of file
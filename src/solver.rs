@@ -0,0 +1,124 @@
+use crate::error::{Error, Result};
+use crate::input::{Input, InputKind};
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11, day12, day13, day14, day15, day16, day17, day18};
+
+pub trait Solver: Sync {
+	fn day(&self) -> i32;
+	fn name(&self) -> &str;
+	fn input_kind(&self) -> InputKind;
+	fn version(&self) -> &str;
+	fn part1(&self, input: &Input) -> Result<String>;
+	fn part2(&self, input: &Input) -> Result<String>;
+
+	fn part(&self, part: i32, input: &Input) -> Result<String> {
+		match part {
+			1 => return self.part1(input),
+			2 => return self.part2(input),
+			x => return Err(Error::new(format!("there is no part {}", x)).with_day(self.day()))
+		}
+	}
+}
+
+pub struct Day {
+	day: i32,
+	name: &'static str,
+	input_kind: InputKind,
+	version: &'static str,
+	part1: fn(&Input) -> Result<String>,
+	part2: fn(&Input) -> Result<String>
+}
+
+impl Solver for Day {
+	fn day(&self) -> i32 { self.day }
+	fn name(&self) -> &str { self.name }
+	fn input_kind(&self) -> InputKind { self.input_kind }
+	fn version(&self) -> &str { self.version }
+	fn part1(&self, input: &Input) -> Result<String> { (self.part1)(input) }
+	fn part2(&self, input: &Input) -> Result<String> { (self.part2)(input) }
+}
+
+macro_rules! input_data {
+	(Lines, $input:expr) => { $input.lines() };
+	(Chunk, $input:expr) => { $input.chunk() };
+	(Numbers, $input:expr) => { $input.numbers() };
+	(Records, $input:expr) => { $input.records() };
+}
+
+// Each day module has a VERSION that's part of the cache key for its answers, so bump it whenever a change to the
+// solver could change what it returns
+macro_rules! register {
+	($day_mod:ident, $day_value:expr, $name:expr, $kind:ident) => {
+		Day {
+			day: $day_value,
+			name: $name,
+			input_kind: InputKind::$kind,
+			version: $day_mod::VERSION,
+			part1: |input| $day_mod::part1(input_data!($kind, input)).map(|answer| answer.to_string()),
+			part2: |input| $day_mod::part2(input_data!($kind, input)).map(|answer| answer.to_string())
+		}
+	}
+}
+
+static DAYS: &[Day] = &[
+	register!(day1, 1, "Report Repair", Numbers),
+	register!(day2, 2, "Password Philosophy", Lines),
+	register!(day3, 3, "Toboggan Trajectory", Lines),
+	register!(day4, 4, "Passport Processing", Records),
+	register!(day5, 5, "Binary Boarding", Lines),
+	register!(day6, 6, "Custom Customs", Records),
+	register!(day7, 7, "Handy Haversacks", Lines),
+	register!(day8, 8, "Handheld Halting", Lines),
+	register!(day9, 9, "Encoding Error", Lines),
+	register!(day10, 10, "Adapter Array", Lines),
+	register!(day11, 11, "Seating System", Lines),
+	register!(day12, 12, "Rain Risk", Lines),
+	register!(day13, 13, "Shuttle Search", Lines),
+	register!(day14, 14, "Docking Data", Lines),
+	register!(day15, 15, "Rambunctious Recitation", Chunk),
+	register!(day16, 16, "Ticket Translation", Records),
+	register!(day17, 17, "Conway Cubes", Lines),
+	register!(day18, 18, "Operation Order", Lines),
+];
+
+pub fn solvers() -> Vec<&'static dyn Solver> {
+	return DAYS.iter().map(|day| day as &dyn Solver).collect();
+}
+
+pub fn find(day: i32) -> Option<&'static dyn Solver> {
+	return solvers().into_iter().find(|solver| solver.day() == day);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn registry_is_ordered_and_unique() {
+		let days: Vec<i32> = solvers().iter().map(|solver| solver.day()).collect();
+		let mut sorted = days.clone();
+		sorted.sort_unstable();
+		sorted.dedup();
+
+		assert_eq!(days, sorted);
+		assert!(solvers().iter().all(|solver| !solver.name().is_empty() && !solver.version().is_empty()));
+	}
+
+	#[test]
+	fn solvers_run_through_the_registry() {
+		let input = Input::Lines("nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6".lines().map(String::from).collect());
+
+		let solver = find(8).unwrap();
+		assert_eq!(solver.input_kind(), InputKind::Lines);
+		assert_eq!(solver.part(1, &input).unwrap(), "5");
+		assert_eq!(solver.part(2, &input).unwrap(), "8");
+		assert!(find(25).is_none());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,273 @@
+use std::{
+	cell::RefCell,
+	fs,
+	io::{self, Write},
+	path::PathBuf,
+	thread,
+	time::Duration
+};
+
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+
+// Long enough to follow a simulation in the terminal without making the bigger days take forever
+pub const FRAME_DELAY: Duration = Duration::from_millis(60);
+// Each cell becomes a square of this many pixels in image frames
+pub const PIXELS_PER_CELL: usize = 3;
+
+// One picture of a solver's state; the characters are the ones the day itself uses, and PALETTE colours them
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+	pub title: String,
+	pub cells: Grid<char>
+}
+
+impl Frame {
+	pub fn new(title: impl Into<String>, cells: Grid<char>) -> Frame {
+		return Frame { title: title.into(), cells };
+	}
+}
+
+const PALETTE: &[(char, (u8, u8, u8))] = &[
+	('#', (230, 230, 230)),
+	('.', (40, 40, 40)),
+	('L', (60, 170, 60)),
+	('O', (70, 130, 230)),
+	('X', (220, 50, 50)),
+	('*', (240, 200, 60)),
+	('S', (60, 170, 60)),
+	(' ', (0, 0, 0))
+];
+
+pub fn color(c: char) -> (u8, u8, u8) {
+	return PALETTE.iter().find(|(key, _)| *key == c).map(|(_, color)| *color).unwrap_or((128, 128, 128));
+}
+
+// Where frames end up: redrawn in place on stderr, so they don't get mixed into the results on stdout, or written
+// out as numbered netpbm images that most image viewers and ffmpeg can read
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+	Terminal,
+	Images(PathBuf)
+}
+
+impl Target {
+	pub fn sink(&self, day: i32, part: i32) -> Result<Box<dyn Sink>> {
+		match self {
+			Target::Terminal => return Ok(Box::new(Terminal { out: Box::new(io::stderr()), delay: FRAME_DELAY })),
+			Target::Images(dir) => {
+				fs::create_dir_all(dir).map_err(|e| Error::new(format!("couldn't create {}: {}", dir.display(), e)))?;
+				return Ok(Box::new(Images { dir: dir.clone(), prefix: format!("day{}-part{}", day, part), count: 0 }));
+			}
+		}
+	}
+}
+
+pub trait Sink {
+	fn frame(&mut self, frame: &Frame) -> io::Result<()>;
+}
+
+pub struct Terminal {
+	out: Box<dyn Write>,
+	delay: Duration
+}
+
+impl Sink for Terminal {
+	fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+		self.out.write_all(ansi(frame).as_bytes())?;
+		self.out.flush()?;
+		thread::sleep(self.delay);
+		return Ok(());
+	}
+}
+
+// Clears the screen and draws the frame from the top left, each cell in its palette colour
+pub fn ansi(frame: &Frame) -> String {
+	let mut text = format!("\x1b[2J\x1b[H{}\n", frame.title);
+	for row in frame.cells.render(|c| *c).lines() {
+		// Runs of the same character only need their colour set once
+		let mut previous = None;
+		for c in row.chars() {
+			if previous != Some(c) {
+				let (r, g, b) = color(c);
+				text += &format!("\x1b[38;2;{};{};{}m", r, g, b);
+			}
+			text.push(c);
+			previous = Some(c);
+		}
+		text += "\x1b[0m\n";
+	}
+
+	return text;
+}
+
+pub struct Images {
+	dir: PathBuf,
+	prefix: String,
+	count: usize
+}
+
+impl Sink for Images {
+	fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+		self.count += 1;
+		let path = self.dir.join(format!("{}-{:05}.ppm", self.prefix, self.count));
+		return fs::write(path, ppm(&frame.cells, PIXELS_PER_CELL));
+	}
+}
+
+// Binary PPM (P6): a short text header and then three bytes per pixel, row by row
+pub fn ppm(cells: &Grid<char>, scale: usize) -> Vec<u8> {
+	let (width, height) = (cells.width() * scale, cells.height() * scale);
+	let mut bytes = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+	bytes.reserve(width * height * 3);
+
+	for y in 0..height {
+		for x in 0..width {
+			let (r, g, b) = color(cells[(x / scale, y / scale)]);
+			bytes.extend_from_slice(&[r, g, b]);
+		}
+	}
+
+	return bytes;
+}
+
+struct Recorder {
+	sink: Box<dyn Sink>,
+	error: Option<io::Error>
+}
+
+thread_local! {
+	static TARGET: RefCell<Option<Target>> = const { RefCell::new(None) };
+	static CURRENT: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+// The runner sets the target for each day's thread, the same way it hands out cancellation tokens
+pub fn with_target<T, F>(target: Option<&Target>, f: F) -> T where F: FnOnce() -> T {
+	let previous = TARGET.with(|current| current.replace(target.cloned()));
+	let result = f();
+	TARGET.with(|current| *current.borrow_mut() = previous);
+
+	return result;
+}
+
+// Each part gets its own sink, so image frames are numbered per part
+pub fn for_part<T, F>(day: i32, part: i32, f: F) -> Result<T> where F: FnOnce() -> Result<T> {
+	match TARGET.with(|current| current.borrow().clone()) {
+		Some(target) => return with_sink(target.sink(day, part)?, f),
+		None => return f()
+	}
+}
+
+// Frames from `f` go to the sink; a sink that fails stops taking frames and the failure comes back as the result
+pub fn with_sink<T, F>(sink: Box<dyn Sink>, f: F) -> Result<T> where F: FnOnce() -> Result<T> {
+	let previous = CURRENT.with(|current| current.replace(Some(Recorder { sink, error: None })));
+	let result = f();
+	let recorder = CURRENT.with(|current| current.replace(previous));
+
+	match recorder.and_then(|recorder| recorder.error) {
+		Some(e) => return Err(Error::new(format!("couldn't write a frame: {}", e))),
+		None => return result
+	}
+}
+
+pub fn enabled() -> bool {
+	return CURRENT.with(|current| current.borrow().is_some());
+}
+
+// Frames are only built when something is watching, so solvers can call this on every step for free otherwise
+pub fn show<F>(frame: F) where F: FnOnce() -> Frame {
+	CURRENT.with(|current| {
+		if let Some(recorder) = current.borrow_mut().as_mut() {
+			if recorder.error.is_some() { return; }
+			if let Err(e) = recorder.sink.frame(&frame()) {
+				recorder.error = Some(e);
+			}
+		}
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::{Arc, Mutex};
+
+	struct Collect(Arc<Mutex<Vec<String>>>);
+
+	impl Sink for Collect {
+		fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+			self.0.lock().unwrap().push(frame.title.clone());
+			return Ok(());
+		}
+	}
+
+	struct Broken;
+
+	impl Sink for Broken {
+		fn frame(&mut self, _: &Frame) -> io::Result<()> {
+			return Err(io::Error::other("disk full"));
+		}
+	}
+
+	fn frame(title: &str) -> Frame {
+		return Frame::new(title, Grid::parse(&[String::from("#.L"), String::from("OX*")], Some).unwrap());
+	}
+
+	#[test]
+	fn frames_only_go_to_the_current_sink() {
+		let titles = Arc::new(Mutex::new(vec![]));
+		show(|| panic!("nothing is watching"));
+		assert!(!enabled());
+
+		let answer = with_sink(Box::new(Collect(titles.clone())), || {
+			assert!(enabled());
+			show(|| frame("one"));
+			show(|| frame("two"));
+			Ok(5)
+		});
+
+		assert_eq!(answer.unwrap(), 5);
+		assert_eq!(*titles.lock().unwrap(), vec!["one", "two"]);
+		assert!(!enabled());
+	}
+
+	#[test]
+	fn broken_sinks_are_reported() {
+		let result = with_sink(Box::new(Broken), || {
+			show(|| frame("one"));
+			show(|| panic!("the sink already failed"));
+			Ok(())
+		});
+
+		assert_eq!(result.unwrap_err().message, "couldn't write a frame: disk full");
+	}
+
+	#[test]
+	fn frames_render() {
+		let image = ppm(&frame("one").cells, 2);
+		assert!(image.starts_with(b"P6\n6 4\n255\n"));
+		assert_eq!(image.len(), "P6\n6 4\n255\n".len() + 6 * 4 * 3);
+		assert_eq!(&image[image.len() - 3..], &[240, 200, 60]);
+
+		let text = ansi(&frame("one"));
+		assert!(text.starts_with("\x1b[2J\x1b[Hone\n\x1b[38;2;230;230;230m#"));
+		assert_eq!(text.matches("\x1b[0m\n").count(), 2);
+		assert_eq!(ansi(&Frame::new("", Grid::new(3, 1, '#'))), "\x1b[2J\x1b[H\n\x1b[38;2;230;230;230m###\x1b[0m\n");
+	}
+
+	#[test]
+	fn images_are_numbered_per_part() {
+		let dir = std::env::temp_dir().join(format!("advent2020-frames-{}", std::process::id()));
+		let target = Target::Images(dir.clone());
+
+		with_target(Some(&target), || for_part(11, 2, || {
+			show(|| frame("one"));
+			show(|| frame("two"));
+			Ok(())
+		})).unwrap();
+		assert!(for_part(11, 1, || Ok(enabled())).is_ok_and(|enabled| !enabled));
+
+		assert!(dir.join("day11-part2-00001.ppm").exists());
+		assert!(dir.join("day11-part2-00002.ppm").exists());
+		fs::remove_dir_all(&dir).unwrap();
+	}
+}
\ No newline at end of file
@@ -0,0 +1,201 @@
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	time::{Duration, SystemTime}
+};
+
+use tokio::time;
+
+use advent2020::fixtures;
+use advent2020::input::Source;
+use advent2020::results::{PartResult, Status};
+use advent2020::runner;
+use advent2020::solver::{self, Solver};
+
+use crate::cli::Options;
+use crate::output;
+
+// Polling keeps this portable and dependency free, and half a second is well under how long an edit takes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type Snapshot = HashMap<PathBuf, Option<SystemTime>>;
+
+// The day's puzzle input plus everything in its examples directory, so new fixtures are picked up as they appear
+fn watched_files(options: &Options, day: i32) -> Vec<PathBuf> {
+	let mut files = vec![];
+	if let Source::File(path) = options.source(day) {
+		files.push(path);
+	}
+
+	if let Ok(entries) = fs::read_dir(Path::new(fixtures::ROOT).join(format!("day{}", day))) {
+		files.extend(entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()));
+	}
+
+	files.sort();
+	return files;
+}
+
+// A file that can't be read is recorded as None, so one that disappears or reappears counts as a change too
+fn snapshot(files: &[PathBuf]) -> Snapshot {
+	return files.iter().map(|path| (path.clone(), fs::metadata(path).and_then(|metadata| metadata.modified()).ok())).collect();
+}
+
+fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+	let mut changed: Vec<PathBuf> = after.iter().filter(|(path, modified)| before.get(*path) != Some(modified)).map(|(path, _)| path.clone()).collect();
+	changed.extend(before.keys().filter(|path| !after.contains_key(*path)).cloned());
+	changed.sort();
+
+	return changed;
+}
+
+fn part_line(label: &str, result: &PartResult) -> String {
+	match &result.status {
+		Status::Solved => return format!("{} part {} result: {} ({} ms)", label, result.part, result.answer, output::elapsed_ms(result)),
+		Status::Failed(error) => return format!("{} part {} FAILED: {} ({} ms)", label, result.part, error, output::elapsed_ms(result)),
+		Status::TimedOut(error) => return format!("{} part {} TIMED OUT: {}", label, result.part, error)
+	}
+}
+
+// Never cached, since watching usually means the solver is being edited without its VERSION changing
+async fn run_input(options: &Options, solver: &'static dyn Solver) {
+	let results = runner::run_days(vec![(solver, options.source(solver.day()))], &options.parts, 1, options.timeout, None, None, false).await;
+
+	for result in results.iter().flat_map(|day| day.parts.iter()) {
+		println!("{}", part_line(&format!("Day {}", solver.day()), result));
+	}
+}
+
+// Examples go through the runner like the input does, so an edit that leaves a day looping forever is stopped by
+// --timeout instead of hanging the watch
+async fn example_lines(options: &Options, solver: &'static dyn Solver, root: &Path) -> Vec<String> {
+	let found = match fixtures::for_day(root, solver.day()) {
+		Ok(found) => found,
+		Err(e) => return vec![format!("Day {} examples: {}", solver.day(), e)]
+	};
+
+	let mut lines = vec![];
+	for fixture in &found {
+		let results = runner::run_days(vec![(solver, Source::File(fixture.input.clone()))], &fixture.parts(), 1, options.timeout, None, None, false).await;
+		for outcome in results.into_iter().flat_map(|day| fixture.outcomes(day)) {
+			let verdict = if outcome.passed() { String::from("PASS") } else { format!("FAIL, expected {}", outcome.expected) };
+			lines.push(format!("{}, {}", part_line(&format!("Day {} {}", solver.day(), fixture.input.file_name().unwrap_or_default().to_string_lossy()), &outcome.result), verdict));
+		}
+	}
+
+	return lines;
+}
+
+async fn run_examples(options: &Options, solver: &'static dyn Solver) {
+	for line in example_lines(options, solver, Path::new(fixtures::ROOT)).await {
+		println!("{}", line);
+	}
+}
+
+// Runs every selected day once, then only the days whose files change, until the process is interrupted
+pub async fn watch(options: &Options) {
+	let mut snapshots = HashMap::new();
+	for solver in options.days.iter().filter_map(|day| solver::find(*day)) {
+		snapshots.insert(solver.day(), snapshot(&watched_files(options, solver.day())));
+		run_input(options, solver).await;
+		run_examples(options, solver).await;
+	}
+	println!("Watching for changes, press Ctrl-C to stop");
+
+	loop {
+		time::sleep(POLL_INTERVAL).await;
+
+		for solver in options.days.iter().filter_map(|day| solver::find(*day)) {
+			let current = snapshot(&watched_files(options, solver.day()));
+			let changed = changed_files(&snapshots[&solver.day()], &current);
+			if changed.is_empty() { continue; }
+
+			for path in &changed {
+				println!("{} changed", path.display());
+			}
+
+			let input = options.source(solver.day());
+			if changed.iter().any(|path| Source::File(path.clone()) == input) {
+				run_input(options, solver).await;
+			}
+			if changed.iter().any(|path| Source::File(path.clone()) != input) {
+				run_examples(options, solver).await;
+			}
+
+			snapshots.insert(solver.day(), current);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use advent2020::cancel;
+	use advent2020::error::Result;
+	use advent2020::input::{Input, InputKind};
+	use crate::cli;
+
+	#[test]
+	fn days_watch_their_input_and_examples() {
+		let available: Vec<i32> = (1..=18).collect();
+		let options = cli::parse_args(vec![String::from("--day"), String::from("13")], &available, None).unwrap();
+		let files = watched_files(&options, 13);
+
+		assert!(files.contains(&PathBuf::from("data/day13.txt")));
+		assert!(files.contains(&PathBuf::from("data/examples/day13/example.txt")));
+		assert!(files.contains(&PathBuf::from("data/examples/day13/example.expected")));
+	}
+
+	struct Stuck;
+
+	impl Solver for Stuck {
+		fn day(&self) -> i32 { 98 }
+		fn name(&self) -> &str { "Stuck" }
+		fn input_kind(&self) -> InputKind { InputKind::Chunk }
+		fn version(&self) -> &str { "1" }
+		fn part1(&self, _: &Input) -> Result<String> {
+			loop {
+				cancel::check()?;
+				std::thread::sleep(Duration::from_millis(1));
+			}
+		}
+		fn part2(&self, _: &Input) -> Result<String> { Ok(String::new()) }
+	}
+
+	#[tokio::test]
+	async fn stuck_examples_time_out() {
+		let root = std::env::temp_dir().join(format!("advent2020-watch-examples-{}", std::process::id()));
+		fs::create_dir_all(root.join("day98")).unwrap();
+		fs::write(root.join("day98/example.txt"), "1\n").unwrap();
+		fs::write(root.join("day98/example.expected"), "part1 = 1\n").unwrap();
+
+		let available: Vec<i32> = (1..=18).collect();
+		let mut options = cli::parse_args(vec![String::from("--day"), String::from("13")], &available, None).unwrap();
+		options.timeout = Some(Duration::from_millis(50));
+
+		assert_eq!(example_lines(&options, &Stuck, &root).await, vec!["Day 98 example.txt part 1 TIMED OUT: day 98: no answer within 50ms, FAIL, expected 1"]);
+		fs::remove_dir_all(&root).unwrap();
+	}
+
+	#[test]
+	fn changes_are_noticed() {
+		let path = std::env::temp_dir().join(format!("advent2020-watch-{}.txt", std::process::id()));
+		let missing = std::env::temp_dir().join(format!("advent2020-watch-{}-missing.txt", std::process::id()));
+		let files = vec![path.clone(), missing.clone()];
+
+		fs::write(&path, "1").unwrap();
+		let before = snapshot(&files);
+		assert!(changed_files(&before, &snapshot(&files)).is_empty());
+
+		let file = fs::File::options().write(true).open(&path).unwrap();
+		file.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+		assert_eq!(changed_files(&before, &snapshot(&files)), vec![path.clone()]);
+
+		fs::write(&missing, "2").unwrap();
+		assert_eq!(changed_files(&before, &snapshot(&files)).len(), 2);
+		assert_eq!(changed_files(&before, &snapshot(&files[..1])).len(), 2);
+
+		fs::remove_file(&path).unwrap();
+		fs::remove_file(&missing).unwrap();
+	}
+}
\ No newline at end of file
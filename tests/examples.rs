@@ -0,0 +1,33 @@
+// Explicit returns are the house style
+#![allow(clippy::needless_return)]
+
+use std::path::Path;
+
+use advent2020::{fixtures, solver};
+
+// Every example under data/examples is run through the registered solver for its day
+#[test]
+fn examples_match_their_expected_answers() {
+	let root = Path::new(env!("CARGO_MANIFEST_DIR")).join(fixtures::ROOT);
+	let found = fixtures::discover(&root).unwrap();
+	assert!(!found.is_empty(), "no fixtures found under {}", root.display());
+
+	let mut failures = vec![];
+	for fixture in &found {
+		let name = fixture.input.strip_prefix(env!("CARGO_MANIFEST_DIR")).unwrap_or(&fixture.input).display().to_string();
+		let solver = match solver::find(fixture.day) {
+			Some(solver) => solver,
+			None => {
+				failures.push(format!("{}: day {} isn't registered", name, fixture.day));
+				continue;
+			}
+		};
+
+		for outcome in fixtures::run(fixture, solver).iter().filter(|outcome| !outcome.passed()) {
+			let got = outcome.result.status.error().unwrap_or(&outcome.result.answer);
+			failures.push(format!("{} part {}: got {}, expected {}", name, outcome.result.part, got, outcome.expected));
+		}
+	}
+
+	assert!(failures.is_empty(), "{} of the example answers were wrong:\n{}", failures.len(), failures.join("\n"));
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+// Explicit returns are the house style
+#![allow(clippy::needless_return)]
+
+use advent2020::day8::{Console, TerminationReason};
+use advent2020::day12::Ship;
+use advent2020::input::Input;
+use advent2020::solver;
+
+fn lines(text: &str) -> Vec<String> {
+	return text.lines().map(String::from).collect();
+}
+
+#[test]
+fn console_is_usable_directly() {
+	let mut console = Console::new(&lines("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6")).unwrap();
+	assert!(console.run().unwrap() == (5, TerminationReason::LoopDetected));
+
+	console.reset();
+	assert!(console.swap_and_run(7).unwrap() == (8, TerminationReason::Completed));
+}
+
+#[test]
+fn ship_is_usable_directly() {
+	let mut ship = Ship::new();
+	ship.move_ship('F', 10).unwrap();
+	ship.move_ship('N', 3).unwrap();
+	assert_eq!(ship.distance().unwrap(), 13);
+}
+
+#[test]
+fn solvers_run_through_the_registry() {
+	let solver = solver::find(1).unwrap();
+	let input = Input::parse(solver.input_kind(), String::from("1721\n979\n366\n299\n675\n1456\n")).unwrap();
+	assert_eq!(solver.part1(&input).unwrap(), "514579");
+	assert_eq!(solver.part2(&input).unwrap(), "241861950");
+}
\ No newline at end of file